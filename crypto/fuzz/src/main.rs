@@ -9,10 +9,14 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-use crypto::{primitives::cipher::AeadCipher, ChaChaPolyIetf, XChaChaPoly};
+use crypto::{
+    primitives::{auth::VarLenMessageAuthCode, cipher::AeadCipher, hash::VarLenHash},
+    Blake2b, ChaChaPolyIetf, XChaChaPoly,
+};
 use hex::ToHex;
 use sodiumoxide::crypto::{
     aead::{chacha20poly1305_ietf, xchacha20poly1305_ietf},
+    generichash,
     stream::salsa20,
 };
 
@@ -48,6 +52,12 @@ struct XChaChaPolyVector {
     plain: Vec<u8>,
     ad: Vec<u8>,
 }
+// Blake2b Test Vector.
+struct Blake2bVector {
+    key: Vec<u8>,
+    data: Vec<u8>,
+    hash_len: usize,
+}
 
 impl SecureRng {
     // create a new SecureRNG
@@ -102,7 +112,7 @@ impl SecureRng {
     }
 }
 
-impl ChaChaPolyTestVector {
+impl ChaChaPolyVector {
     // generate a random test vector.
     pub fn random(limit: usize, rng: &mut SecureRng) -> Self {
         Self {
@@ -156,7 +166,7 @@ impl ChaChaPolyTestVector {
     }
 }
 
-impl XChaChaPolyTestVector {
+impl XChaChaPolyVector {
     // generate a random test vector.
     pub fn random(limit: usize, rng: &mut SecureRng) -> Self {
         Self {
@@ -209,13 +219,61 @@ impl XChaChaPolyTestVector {
     }
 }
 
+impl Blake2bVector {
+    // generate a random test vector.
+    pub fn random(limit: usize, rng: &mut SecureRng) -> Self {
+        // libsodium only supports keys and hashes with 16 to 64 bytes.
+        let key = match rng.random_range(0..2) {
+            0 => Vec::new(),
+            _ => rng.random_len_vec(16..65),
+        };
+        Self {
+            key,
+            data: rng.random_len_vec(0..limit),
+            hash_len: rng.random_range(16..65) as usize,
+        }
+    }
+
+    pub fn test(self) {
+        // hash the data using Blake2b
+        let mut hash_crypto = vec![0u8; self.hash_len];
+        if self.key.is_empty() {
+            Blake2b.var_len_hash(&mut hash_crypto, &self.data).unwrap();
+        } else {
+            Blake2b.varlen_auth(&mut hash_crypto, &self.data, &self.key).unwrap();
+        }
+
+        // hash the data with Sodiumoxide
+        let hash_sodium = generichash::hash(
+            &self.data,
+            Some(self.hash_len),
+            if self.key.len() > 0 { Some(&self.key) } else { None },
+        )
+        .unwrap();
+
+        // Compare the hashes.
+        if hash_crypto.as_slice() != hash_sodium.as_ref() {
+            eprintln!("Error Report:");
+            eprintln!("Key: {}", self.key.encode_hex::<String>());
+            eprintln!("Data: {}", self.data.encode_hex::<String>());
+            eprintln!("Hash length: {}", self.hash_len);
+            eprintln!("Outputs:");
+            eprintln!("Crypto: {}", hash_crypto.encode_hex::<String>());
+            eprintln!("Sodiumoxide: {}", hash_sodium.as_ref().encode_hex::<String>());
+            panic!("Exiting. Please save this error information.");
+        }
+        // increment the counter.
+        COUNTER.fetch_add(1, Relaxed);
+    }
+}
+
 fn main() {
     // get the threads from the THREADS enviroment var.
     let threads_str = env::var("NUM_THREADS").unwrap_or(num_cpus::get().to_string());
     let threads = usize::from_str(&threads_str).expect("Invalid value of THREADS");
 
     // load the enviroment limit from the VECTOR_LIMIT env var.
-    let limit_str = env::var("VECTOR_LIMIT").unwrap_or(264.to_string());
+    let limit_str = env::var("VECTOR_LIMIT").unwrap_or(1100.to_string());
     let limit = usize::from_str(&limit_str).expect("Invalid value of TEST_VECTOR_LIMIT");
 
     // fuzz the threads.
@@ -223,7 +281,8 @@ fn main() {
         let mut rng = SecureRng::new();
         thread::spawn(move || loop {
             ChaChaPolyVector::random(limit, &mut rng).test();
-            XChaChaPolyVector::random(limit, &mut rng).test()
+            XChaChaPolyVector::random(limit, &mut rng).test();
+            Blake2bVector::random(limit, &mut rng).test()
         });
     }
 
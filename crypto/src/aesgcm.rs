@@ -0,0 +1,181 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::aesgcm::{aes256gcm_open, aes256gcm_seal, Aes256GcmSoft};
+#[cfg(target_arch = "x86_64")]
+use crate::internal::aesni::Aes256GcmNi;
+use primitives::{
+    cipher::{AeadCipher, Cipher, CipherInfo},
+    rng::{SecretKeyGen, SecureRng},
+};
+
+use crate::Error;
+
+/// max bytes that can be processed with a key/nonce combo
+#[cfg(target_pointer_width = "64")]
+pub const AES256GCM_MAX: usize = (4_294_967_296 - 2) * 16;
+#[cfg(target_pointer_width = "32")]
+pub const AES256GCM_MAX: usize = usize::MAX - 16;
+/// size of the key
+pub const AES256GCM_KEY: usize = 32;
+/// size of the nonce
+pub const AES256GCM_NONCE: usize = 12;
+/// size of the auth tag
+pub const AES256GCM_TAG: usize = 16;
+
+/// AES-256-GCM cipher. Uses AES-NI and PCLMULQDQ if the CPU supports them and a constant time bitsliced
+/// implementation otherwise.
+pub struct Aes256Gcm {
+    soft: bool,
+}
+
+impl Aes256Gcm {
+    /// creates a new Cipher with AES-256-GCM
+    pub fn cipher() -> Box<dyn Cipher> {
+        Box::new(Self { soft: false })
+    }
+
+    /// creates an AEAD Cipher with AES-256-GCM
+    pub fn aead_cipher() -> Box<dyn AeadCipher> {
+        Box::new(Self { soft: false })
+    }
+
+    /// creates an AEAD Cipher with AES-256-GCM that always uses the bitsliced software implementation
+    pub fn software_aead_cipher() -> Box<dyn AeadCipher> {
+        Box::new(Self { soft: true })
+    }
+
+    /// indicates whether or not the CPU supports the AES-NI and PCLMULQDQ instructions
+    pub fn is_accelerated() -> bool {
+        #[cfg(target_arch = "x86_64")]
+        {
+            is_x86_feature_detected!("aes")
+                && is_x86_feature_detected!("pclmulqdq")
+                && is_x86_feature_detected!("ssse3")
+        }
+        #[cfg(not(target_arch = "x86_64"))]
+        {
+            false
+        }
+    }
+
+    /// encrypts data in place and authenticates it with the fastest available implementation
+    fn seal_in_place(&self, data: &mut [u8], tag: &mut [u8], ad: &[u8], key: &[u8], nonce: &[u8]) {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if !self.soft {
+                if let Some(core) = Aes256GcmNi::new(key) {
+                    return aes256gcm_seal(&core, data, tag, ad, nonce);
+                }
+            }
+        }
+        aes256gcm_seal(&Aes256GcmSoft::new(key), data, tag, ad, nonce)
+    }
+
+    /// decrypts data in place after validation with the fastest available implementation
+    fn open_in_place(&self, data: &mut [u8], tag: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<(), Error> {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if !self.soft {
+                if let Some(core) = Aes256GcmNi::new(key) {
+                    return aes256gcm_open(&core, data, tag, ad, nonce);
+                }
+            }
+        }
+        aes256gcm_open(&Aes256GcmSoft::new(key), data, tag, ad, nonce)
+    }
+}
+
+impl SecretKeyGen for Aes256Gcm {
+    /// generate a new secret key
+    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Error> {
+        verify_keygen!(AES256GCM_KEY => buf);
+
+        rng.random(&mut buf[..AES256GCM_KEY])?;
+        Ok(AES256GCM_KEY)
+    }
+}
+
+impl Cipher for Aes256Gcm {
+    fn info(&self) -> CipherInfo {
+        CipherInfo {
+            id: "Aes256Gcm",
+            one_time: true,
+            key_lens: AES256GCM_KEY..AES256GCM_KEY,
+            nonce_lens: AES256GCM_NONCE..AES256GCM_NONCE,
+            tag_lens: AES256GCM_TAG..AES256GCM_TAG,
+        }
+    }
+
+    fn predict_encrypted_max(&self, plain_len: usize) -> usize {
+        plain_len + AES256GCM_TAG
+    }
+
+    fn encrypt(&self, buf: &mut [u8], plain_len: usize, key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        self.seal(buf, plain_len, &[], key, nonce)
+    }
+    fn encrypt_to(&self, buf: &mut [u8], plain: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        self.seal_with(buf, plain, &[], key, nonce)
+    }
+
+    fn decrypt(&self, buf: &mut [u8], cipher_len: usize, key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        self.open(buf, cipher_len, &[], key, nonce)
+    }
+    fn decrypt_to(&self, buf: &mut [u8], cipher: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        self.open_to(buf, cipher, &[], key, nonce)
+    }
+}
+
+impl AeadCipher for Aes256Gcm {
+    fn seal(&self, buf: &mut [u8], plain_len: usize, ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        verify_seal!(
+            key => [AES256GCM_KEY], nonce => [AES256GCM_NONCE],
+            plain_len => [buf, AES256GCM_TAG, AES256GCM_MAX]
+        );
+
+        let (data, tag) = buf.split_at_mut(plain_len);
+        self.seal_in_place(data, &mut tag[..AES256GCM_TAG], ad, key, nonce);
+        Ok(plain_len + AES256GCM_TAG)
+    }
+    fn seal_with(&self, buf: &mut [u8], plain: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        verify_seal!(
+            key => [AES256GCM_KEY], nonce => [AES256GCM_NONCE],
+            plain => [buf, AES256GCM_TAG, AES256GCM_MAX]
+        );
+
+        let (data, tag) = buf.split_at_mut(plain.len());
+        data.copy_from_slice(plain);
+        self.seal_in_place(data, &mut tag[..AES256GCM_TAG], ad, key, nonce);
+        Ok(plain.len() + AES256GCM_TAG)
+    }
+
+    fn open(&self, buf: &mut [u8], cipher_len: usize, ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        verify_open!(
+            key => [AES256GCM_KEY], nonce => [AES256GCM_NONCE],
+            cipher_len => [buf, AES256GCM_TAG, AES256GCM_MAX + AES256GCM_TAG]
+        );
+
+        let (data, tag) = buf.split_at_mut(cipher_len - AES256GCM_TAG);
+        self.open_in_place(data, &tag[..AES256GCM_TAG], ad, key, nonce)?;
+        Ok(cipher_len - AES256GCM_TAG)
+    }
+    fn open_to(&self, buf: &mut [u8], cipher: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        verify_open!(
+            key => [AES256GCM_KEY], nonce => [AES256GCM_NONCE],
+            cipher => [buf, AES256GCM_TAG, AES256GCM_MAX + AES256GCM_TAG]
+        );
+
+        let (data, tag) = cipher.split_at(cipher.len() - AES256GCM_TAG);
+        buf[..data.len()].copy_from_slice(data);
+        self.open_in_place(&mut buf[..data.len()], &tag[..AES256GCM_TAG], ad, key, nonce)?;
+        Ok(cipher.len() - AES256GCM_TAG)
+    }
+}
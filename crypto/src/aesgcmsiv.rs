@@ -0,0 +1,181 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+#[cfg(target_arch = "x86_64")]
+use crate::internal::aesni::Aes256GcmNi;
+use crate::internal::{
+    aesgcm::Aes256GcmSoft,
+    aesgcmsiv::{aes256gcmsiv_open, aes256gcmsiv_seal, siv_derive_keys},
+};
+use primitives::{
+    cipher::{AeadCipher, Cipher, CipherInfo},
+    rng::{SecretKeyGen, SecureRng},
+};
+
+use crate::Error;
+
+/// max bytes that can be processed with a key/nonce combo
+#[cfg(target_pointer_width = "64")]
+pub const AES256GCMSIV_MAX: usize = 68_719_476_736;
+#[cfg(target_pointer_width = "32")]
+pub const AES256GCMSIV_MAX: usize = usize::MAX - 16;
+/// size of the key
+pub const AES256GCMSIV_KEY: usize = 32;
+/// size of the nonce
+pub const AES256GCMSIV_NONCE: usize = 12;
+/// size of the auth tag
+pub const AES256GCMSIV_TAG: usize = 16;
+
+/// AES-256-GCM-SIV nonce misuse resistant cipher (RFC 8452). The tag is derived from the nonce, the associated data
+/// and the plaintext, so a repeated nonce only reveals whether two messages are identical. Sealing with a fixed
+/// nonce is deterministic, which allows records to be deduplicated by their content.
+pub struct Aes256GcmSiv {
+    soft: bool,
+}
+
+impl Aes256GcmSiv {
+    /// creates a new Cipher with AES-256-GCM-SIV
+    pub fn cipher() -> Box<dyn Cipher> {
+        Box::new(Self { soft: false })
+    }
+
+    /// creates an AEAD Cipher with AES-256-GCM-SIV
+    pub fn aead_cipher() -> Box<dyn AeadCipher> {
+        Box::new(Self { soft: false })
+    }
+
+    /// creates an AEAD Cipher with AES-256-GCM-SIV that always uses the bitsliced software implementation
+    pub fn software_aead_cipher() -> Box<dyn AeadCipher> {
+        Box::new(Self { soft: true })
+    }
+
+    /// encrypts data in place and authenticates it with the fastest available implementation
+    fn seal_in_place(&self, data: &mut [u8], tag: &mut [u8], ad: &[u8], key: &[u8], nonce: &[u8]) {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if !self.soft {
+                let ni = Aes256GcmNi::new(key).and_then(|core| {
+                    let (auth_key, enc_key) = siv_derive_keys(&core, nonce);
+                    Aes256GcmNi::new(&enc_key).map(|core| (core, auth_key))
+                });
+                if let Some((core, auth_key)) = ni {
+                    return aes256gcmsiv_seal(&core, &auth_key, data, tag, ad, nonce);
+                }
+            }
+        }
+        let (auth_key, enc_key) = siv_derive_keys(&Aes256GcmSoft::new(key), nonce);
+        aes256gcmsiv_seal(&Aes256GcmSoft::new(&enc_key), &auth_key, data, tag, ad, nonce)
+    }
+
+    /// decrypts data in place after validation with the fastest available implementation
+    fn open_in_place(&self, data: &mut [u8], tag: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<(), Error> {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if !self.soft {
+                let ni = Aes256GcmNi::new(key).and_then(|core| {
+                    let (auth_key, enc_key) = siv_derive_keys(&core, nonce);
+                    Aes256GcmNi::new(&enc_key).map(|core| (core, auth_key))
+                });
+                if let Some((core, auth_key)) = ni {
+                    return aes256gcmsiv_open(&core, &auth_key, data, tag, ad, nonce);
+                }
+            }
+        }
+        let (auth_key, enc_key) = siv_derive_keys(&Aes256GcmSoft::new(key), nonce);
+        aes256gcmsiv_open(&Aes256GcmSoft::new(&enc_key), &auth_key, data, tag, ad, nonce)
+    }
+}
+
+impl SecretKeyGen for Aes256GcmSiv {
+    /// generate a new secret key
+    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Error> {
+        verify_keygen!(AES256GCMSIV_KEY => buf);
+
+        rng.random(&mut buf[..AES256GCMSIV_KEY])?;
+        Ok(AES256GCMSIV_KEY)
+    }
+}
+
+impl Cipher for Aes256GcmSiv {
+    fn info(&self) -> CipherInfo {
+        CipherInfo {
+            id: "Aes256GcmSiv",
+            one_time: false,
+            key_lens: AES256GCMSIV_KEY..AES256GCMSIV_KEY,
+            nonce_lens: AES256GCMSIV_NONCE..AES256GCMSIV_NONCE,
+            tag_lens: AES256GCMSIV_TAG..AES256GCMSIV_TAG,
+        }
+    }
+
+    fn predict_encrypted_max(&self, plain_len: usize) -> usize {
+        plain_len + AES256GCMSIV_TAG
+    }
+
+    fn encrypt(&self, buf: &mut [u8], plain_len: usize, key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        self.seal(buf, plain_len, &[], key, nonce)
+    }
+    fn encrypt_to(&self, buf: &mut [u8], plain: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        self.seal_with(buf, plain, &[], key, nonce)
+    }
+
+    fn decrypt(&self, buf: &mut [u8], cipher_len: usize, key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        self.open(buf, cipher_len, &[], key, nonce)
+    }
+    fn decrypt_to(&self, buf: &mut [u8], cipher: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        self.open_to(buf, cipher, &[], key, nonce)
+    }
+}
+
+impl AeadCipher for Aes256GcmSiv {
+    fn seal(&self, buf: &mut [u8], plain_len: usize, ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        verify_seal!(
+            key => [AES256GCMSIV_KEY], nonce => [AES256GCMSIV_NONCE],
+            plain_len => [buf, AES256GCMSIV_TAG, AES256GCMSIV_MAX]
+        );
+
+        let (data, tag) = buf.split_at_mut(plain_len);
+        self.seal_in_place(data, &mut tag[..AES256GCMSIV_TAG], ad, key, nonce);
+        Ok(plain_len + AES256GCMSIV_TAG)
+    }
+    fn seal_with(&self, buf: &mut [u8], plain: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        verify_seal!(
+            key => [AES256GCMSIV_KEY], nonce => [AES256GCMSIV_NONCE],
+            plain => [buf, AES256GCMSIV_TAG, AES256GCMSIV_MAX]
+        );
+
+        let (data, tag) = buf.split_at_mut(plain.len());
+        data.copy_from_slice(plain);
+        self.seal_in_place(data, &mut tag[..AES256GCMSIV_TAG], ad, key, nonce);
+        Ok(plain.len() + AES256GCMSIV_TAG)
+    }
+
+    fn open(&self, buf: &mut [u8], cipher_len: usize, ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        verify_open!(
+            key => [AES256GCMSIV_KEY], nonce => [AES256GCMSIV_NONCE],
+            cipher_len => [buf, AES256GCMSIV_TAG, AES256GCMSIV_MAX + AES256GCMSIV_TAG]
+        );
+
+        let (data, tag) = buf.split_at_mut(cipher_len - AES256GCMSIV_TAG);
+        self.open_in_place(data, &tag[..AES256GCMSIV_TAG], ad, key, nonce)?;
+        Ok(cipher_len - AES256GCMSIV_TAG)
+    }
+    fn open_to(&self, buf: &mut [u8], cipher: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        verify_open!(
+            key => [AES256GCMSIV_KEY], nonce => [AES256GCMSIV_NONCE],
+            cipher => [buf, AES256GCMSIV_TAG, AES256GCMSIV_MAX + AES256GCMSIV_TAG]
+        );
+
+        let (data, tag) = cipher.split_at(cipher.len() - AES256GCMSIV_TAG);
+        buf[..data.len()].copy_from_slice(data);
+        self.open_in_place(&mut buf[..data.len()], &tag[..AES256GCMSIV_TAG], ad, key, nonce)?;
+        Ok(cipher.len() - AES256GCMSIV_TAG)
+    }
+}
@@ -0,0 +1,131 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::argon2::{argon2id, Argon2Params};
+use crate::Error;
+use primitives::pbkdf::{Pbkdf, PbkdfInfo, StatelessPbkdf};
+
+/// min size of the derived output
+pub const ARGON2_OUTPUT_MIN: usize = 4;
+/// max size of the derived output, password, salt, secret and associated data
+pub const ARGON2_INPUT_MAX: usize = u32::MAX as usize;
+/// min size of the salt
+pub const ARGON2_SALT_MIN: usize = 8;
+/// min number of passes
+pub const ARGON2_CPU_COST_MIN: u64 = 1;
+/// max number of passes
+pub const ARGON2_CPU_COST_MAX: u64 = u32::MAX as u64;
+/// min memory size in KiB per lane
+pub const ARGON2_MEMORY_COST_MIN: u64 = 8;
+/// max memory size in KiB
+#[cfg(target_pointer_width = "64")]
+pub const ARGON2_MEMORY_COST_MAX: u64 = u32::MAX as u64;
+/// max memory size in KiB (limited by the address space)
+#[cfg(not(target_pointer_width = "64"))]
+pub const ARGON2_MEMORY_COST_MAX: u64 = isize::MAX as u64 / 1024;
+/// min number of lanes
+pub const ARGON2_PARALLELISM_MIN: u64 = 1;
+/// max number of lanes
+pub const ARGON2_PARALLELISM_MAX: u64 = 0x00ff_ffff;
+/// default number of passes (RFC 9106 second recommended option)
+pub const ARGON2_CPU_COST: u64 = 3;
+/// default memory size in KiB (RFC 9106 second recommended option)
+pub const ARGON2_MEMORY_COST: u64 = 64 * 1024;
+/// default number of lanes
+pub const ARGON2_PARALLELISM: u64 = 4;
+
+/// Argon2id memory hard password based key derivation function (RFC 9106). The lanes are computed sequentially,
+/// the parallelism only affects the output.
+pub struct Argon2id;
+impl Argon2id {
+    /// creates a PBKDF with the default memory cost and parallelism
+    pub fn pbkdf() -> Box<dyn Pbkdf> {
+        Box::new(Self)
+    }
+
+    /// creates a PBKDF with configurable costs
+    pub fn stateless_pbkdf() -> Box<dyn StatelessPbkdf> {
+        Box::new(Self)
+    }
+
+    /// derives the key with an additional `secret` key and associated data `ad`. `cpu_cost` is the number of passes,
+    /// `memory_cost` the memory size in KiB and `parallelism` the number of lanes.
+    #[allow(clippy::too_many_arguments)]
+    pub fn derive_keyed(
+        &self,
+        buf: &mut [u8],
+        password: &[u8],
+        salt: &[u8],
+        secret: &[u8],
+        ad: &[u8],
+        cpu_cost: u64,
+        memory_cost: u64,
+        parallelism: u64,
+    ) -> Result<(), Error> {
+        verify_pbkdf!(
+            => [buf, ARGON2_OUTPUT_MIN, ARGON2_INPUT_MAX],
+            [password, secret, ad] => [ARGON2_INPUT_MAX],
+            salt => [ARGON2_SALT_MIN, ARGON2_INPUT_MAX],
+            cpu_cost => [ARGON2_CPU_COST_MIN, ARGON2_CPU_COST_MAX],
+            parallelism => [ARGON2_PARALLELISM_MIN, ARGON2_PARALLELISM_MAX],
+            memory_cost => [ARGON2_MEMORY_COST_MIN * parallelism, ARGON2_MEMORY_COST_MAX]
+        );
+
+        let params = Argon2Params {
+            password,
+            salt,
+            secret,
+            ad,
+            time_cost: cpu_cost as u32,
+            memory_cost: memory_cost as u32,
+            parallelism: parallelism as u32,
+        };
+        argon2id(buf, &params)
+    }
+}
+
+impl Pbkdf for Argon2id {
+    fn info(&self) -> PbkdfInfo {
+        PbkdfInfo {
+            id: "Argon2id",
+            output_lens: ARGON2_OUTPUT_MIN..ARGON2_INPUT_MAX,
+            password_lens: 0..ARGON2_INPUT_MAX,
+            salt_lens: ARGON2_SALT_MIN..ARGON2_INPUT_MAX,
+            cpu_cost: ARGON2_CPU_COST,
+            cpu_costs: ARGON2_CPU_COST_MIN as usize..ARGON2_CPU_COST_MAX as usize,
+            memory_cost: ARGON2_MEMORY_COST,
+            memory_costs: ARGON2_MEMORY_COST_MIN..ARGON2_MEMORY_COST_MAX,
+            parallelism: ARGON2_PARALLELISM,
+            parallelisms: ARGON2_PARALLELISM_MIN..ARGON2_PARALLELISM_MAX,
+        }
+    }
+
+    /// derives the key using `cpu_cost` as the number of passes with the default memory cost and parallelism.
+    fn derive(&self, buf: &mut [u8], password: &[u8], salt: &[u8], cpu_cost: u64) -> Result<(), Error> {
+        self.derive_stateless(buf, password, salt, cpu_cost, ARGON2_MEMORY_COST, ARGON2_PARALLELISM)
+    }
+}
+
+impl StatelessPbkdf for Argon2id {
+    /// derives the key using `cpu_cost` as the number of passes, `memory_cost` as the memory size in KiB and
+    /// `parallelism` as the number of lanes.
+    fn derive_stateless(
+        &self,
+        buf: &mut [u8],
+        password: &[u8],
+        salt: &[u8],
+        cpu_cost: u64,
+        memory_cost: u64,
+        parallelism: u64,
+    ) -> Result<(), Error> {
+        self.derive_keyed(buf, password, salt, &[], &[], cpu_cost, memory_cost, parallelism)
+    }
+}
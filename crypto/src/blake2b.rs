@@ -0,0 +1,182 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::blake2b::Blake2bState;
+use crate::Error;
+use primitives::{
+    auth::{MacState, MessageAuthCode, MessageAuthCodeInfo, VarLenMessageAuthCode},
+    hash::{Hash, HashInfo, HashState, VarLenHash},
+    rng::{SecretKeyGen, SecureRng},
+};
+
+/// default size of the hash
+pub const BLAKE2B_LEN: usize = 64;
+/// min size of the hash
+pub const BLAKE2B_LEN_MIN: usize = 1;
+/// max size of the hash
+pub const BLAKE2B_LEN_MAX: usize = 64;
+/// default size of the key
+pub const BLAKE2B_KEY: usize = 32;
+/// min size of the key
+pub const BLAKE2B_KEY_MIN: usize = 1;
+/// max size of the key
+pub const BLAKE2B_KEY_MAX: usize = 64;
+
+/// BLAKE2b hash which is a MAC when used with a key
+pub struct Blake2b;
+impl Blake2b {
+    /// creates a new Hash with BLAKE2b
+    pub fn hasher() -> Box<dyn Hash> {
+        Box::new(Self)
+    }
+
+    /// creates a new variable length Hash with BLAKE2b
+    pub fn var_len_hasher() -> Box<dyn VarLenHash> {
+        Box::new(Self)
+    }
+
+    /// creates a keyed BLAKE2b MAC
+    pub fn message_auth_code() -> Box<dyn MessageAuthCode> {
+        Box::new(Self)
+    }
+
+    /// creates a variable length keyed BLAKE2b MAC
+    pub fn var_len_message_auth_code() -> Box<dyn VarLenMessageAuthCode> {
+        Box::new(Self)
+    }
+
+    /// creates a new incremental Hash with BLAKE2b
+    pub fn hash_state() -> Box<dyn HashState> {
+        Box::new(Blake2bHashState(Some(Blake2bState::new(BLAKE2B_LEN, &[]))))
+    }
+
+    /// creates a new incremental keyed BLAKE2b MAC using the `key`
+    pub fn mac_state(key: &[u8]) -> Result<Box<dyn MacState>, Error> {
+        verify_auth!(key => [BLAKE2B_KEY_MIN, BLAKE2B_KEY_MAX]);
+
+        Ok(Box::new(Blake2bMacState(Some(Blake2bState::new(BLAKE2B_LEN, key)))))
+    }
+}
+
+impl Hash for Blake2b {
+    fn info(&self) -> HashInfo {
+        HashInfo {
+            id: "Blake2b",
+            hash_len: BLAKE2B_LEN,
+            hash_lens: BLAKE2B_LEN_MIN..BLAKE2B_LEN_MAX,
+        }
+    }
+
+    fn hash(&self, buf: &mut [u8], data: &[u8]) -> Result<usize, Error> {
+        verify_hash!(=> [buf, BLAKE2B_LEN]);
+
+        let mut state = Blake2bState::new(BLAKE2B_LEN, &[]);
+        state.update(data);
+        state.finish(buf);
+        Ok(BLAKE2B_LEN)
+    }
+}
+
+impl VarLenHash for Blake2b {
+    /// hashes the data into a digest of `buf.len()` bytes.
+    fn var_len_hash(&self, buf: &mut [u8], data: &[u8]) -> Result<usize, Error> {
+        verify_hash!(=> [buf, BLAKE2B_LEN_MIN, BLAKE2B_LEN_MAX]);
+
+        let mut state = Blake2bState::new(buf.len(), &[]);
+        state.update(data);
+        state.finish(buf);
+        Ok(buf.len())
+    }
+}
+
+impl SecretKeyGen for Blake2b {
+    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Error> {
+        verify_keygen!(BLAKE2B_KEY => buf);
+
+        rng.random(&mut buf[..BLAKE2B_KEY])?;
+        Ok(BLAKE2B_KEY)
+    }
+}
+
+impl MessageAuthCode for Blake2b {
+    fn info(&self) -> MessageAuthCodeInfo {
+        MessageAuthCodeInfo {
+            id: "Blake2b",
+            one_time: false,
+            len: BLAKE2B_LEN,
+            mac_lens: BLAKE2B_LEN_MIN..BLAKE2B_LEN_MAX,
+            key_lens: BLAKE2B_KEY_MIN..BLAKE2B_KEY_MAX,
+        }
+    }
+
+    fn auth(&self, buf: &mut [u8], data: &[u8], key: &[u8]) -> Result<usize, Error> {
+        verify_auth!(key => [BLAKE2B_KEY_MIN, BLAKE2B_KEY_MAX], => [buf, BLAKE2B_LEN]);
+
+        let mut state = Blake2bState::new(BLAKE2B_LEN, key);
+        state.update(data);
+        state.finish(buf);
+        Ok(BLAKE2B_LEN)
+    }
+}
+
+impl VarLenMessageAuthCode for Blake2b {
+    /// authenticates the data into a tag of `buf.len()` bytes.
+    fn varlen_auth(&self, buf: &mut [u8], data: &[u8], key: &[u8]) -> Result<usize, Error> {
+        verify_auth!(key => [BLAKE2B_KEY_MIN, BLAKE2B_KEY_MAX], => [buf, BLAKE2B_LEN_MIN, BLAKE2B_LEN_MAX]);
+
+        let mut state = Blake2bState::new(buf.len(), key);
+        state.update(data);
+        state.finish(buf);
+        Ok(buf.len())
+    }
+}
+
+/// incremental BLAKE2b hash
+struct Blake2bHashState(Option<Blake2bState>);
+
+impl HashState for Blake2bHashState {
+    fn info(&self) -> HashInfo {
+        Hash::info(&Blake2b)
+    }
+
+    fn update(&mut self, data: &[u8]) -> Result<(), Error> {
+        verify_state!(self.0.as_mut()).update(data);
+        Ok(())
+    }
+
+    fn finalize(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        verify_hash!(=> [buf, BLAKE2B_LEN]);
+
+        verify_state!(self.0.take()).finish(buf);
+        Ok(BLAKE2B_LEN)
+    }
+}
+
+/// incremental keyed BLAKE2b MAC
+struct Blake2bMacState(Option<Blake2bState>);
+
+impl MacState for Blake2bMacState {
+    fn info(&self) -> MessageAuthCodeInfo {
+        MessageAuthCode::info(&Blake2b)
+    }
+
+    fn update(&mut self, data: &[u8]) -> Result<(), Error> {
+        verify_state!(self.0.as_mut()).update(data);
+        Ok(())
+    }
+
+    fn finalize(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        verify_hash!(=> [buf, BLAKE2B_LEN]);
+
+        verify_state!(self.0.take()).finish(buf);
+        Ok(BLAKE2B_LEN)
+    }
+}
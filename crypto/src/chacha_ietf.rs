@@ -9,12 +9,13 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-use crate::internal::chacha::chacha20_ietf_block;
+use crate::internal::chacha::chacha20_ietf_xor;
+use crate::Error;
 use primitives::{
     cipher::{Cipher, CipherInfo},
     rng::{SecretKeyGen, SecureRng},
 };
-use std::{cmp::min, error::Error};
+use std::convert::TryFrom;
 
 /// max bytes that can be processed with a key/nonce combo
 #[cfg(target_pointer_width = "64")]
@@ -34,29 +35,24 @@ impl ChaCha20Ietf {
     }
 
     /// Xor data with ChaCha20 keystream
-    pub fn xor(key: &[u8], nonce: &[u8], mut n: u32, mut data: &mut [u8]) {
+    pub fn xor(key: &[u8], nonce: &[u8], n: u32, data: &mut [u8]) {
         // verify inputs
         assert_eq!(CHACHA20_KEY, key.len());
         assert_eq!(CHACHA20_NONCE, nonce.len());
 
+        // verify that the block counter doesn't overflow
+        let blocks = (data.len() + 63) / 64;
+        u32::try_from(blocks)
+            .ok()
+            .and_then(|blocks| n.checked_add(blocks))
+            .expect("The ChaCha20-IETF block counter must not exceed 2^32 - 1");
+
         // xor the data
-        let mut buf = vec![0; 64];
-        while !data.is_empty() {
-            // compute blocks
-            chacha20_ietf_block(key, nonce, n, &mut buf);
-            n = n
-                .checked_add(1)
-                .expect("The ChaCha20-IETF block counter must not exceed 2^32 - 1");
-
-            // xor blocks
-            let to_xor = min(data.len(), buf.len());
-            (0..to_xor).for_each(|i| data[i] = xor!(data[i], buf[i]));
-            data = &mut data[to_xor..];
-        }
+        chacha20_ietf_xor(key, nonce, n, data);
     }
 }
 impl SecretKeyGen for ChaCha20Ietf {
-    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Box<dyn Error + 'static>> {
+    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Error> {
         verify_keygen!(CHACHA20_KEY => buf);
 
         rng.random(&mut buf[..CHACHA20_KEY])?;
@@ -79,13 +75,7 @@ impl Cipher for ChaCha20Ietf {
         plain_len
     }
 
-    fn encrypt(
-        &self,
-        buf: &mut [u8],
-        plain_len: usize,
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn encrypt(&self, buf: &mut [u8], plain_len: usize, key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_encrypt!(
             key => [CHACHA20_KEY], nonce => [CHACHA20_NONCE],
             plain_len => [buf, CHACHA20_MAX]
@@ -95,13 +85,7 @@ impl Cipher for ChaCha20Ietf {
         Ok(plain_len)
     }
 
-    fn encrypt_to(
-        &self,
-        buf: &mut [u8],
-        plain: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn encrypt_to(&self, buf: &mut [u8], plain: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_encrypt!(
             key => [CHACHA20_KEY], nonce => [CHACHA20_NONCE],
             plain => [buf, CHACHA20_MAX]
@@ -112,13 +96,7 @@ impl Cipher for ChaCha20Ietf {
         Ok(plain.len())
     }
 
-    fn decrypt(
-        &self,
-        buf: &mut [u8],
-        cipher_len: usize,
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn decrypt(&self, buf: &mut [u8], cipher_len: usize, key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_decrypt!(
             key => [CHACHA20_KEY], nonce => [CHACHA20_NONCE],
             cipher_len => [buf, CHACHA20_MAX]
@@ -127,13 +105,7 @@ impl Cipher for ChaCha20Ietf {
         Self::xor(key, nonce, 0, &mut buf[..cipher_len]);
         Ok(cipher_len)
     }
-    fn decrypt_to(
-        &self,
-        buf: &mut [u8],
-        cipher: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn decrypt_to(&self, buf: &mut [u8], cipher: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_decrypt!(
             key => [CHACHA20_KEY], nonce => [CHACHA20_NONCE],
             cipher => [buf, CHACHA20_MAX]
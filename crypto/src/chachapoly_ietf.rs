@@ -9,12 +9,12 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
+use crate::Error;
 use crate::{chacha_ietf::ChaCha20Ietf, poly::Poly1305};
 use primitives::{
-    cipher::{AeadCipher, Cipher, CipherInfo},
+    cipher::{AeadCipher, Cipher, CipherInfo, DetachedAeadCipher},
     rng::{SecretKeyGen, SecureRng},
 };
-use std::error::Error;
 /// max bytes that can be processed with a key/nonce combo
 #[cfg(target_pointer_width = "64")]
 pub const CHACHAPOLY_MAX: usize = (4_294_967_296 - 1) * 64;
@@ -45,13 +45,7 @@ pub fn chachapoly_seal(data: &mut [u8], tag: &mut [u8], ad: &[u8], key: &[u8], n
 }
 
 /// open data and decrypt it in place.
-pub fn chachapoly_open(
-    data: &mut [u8],
-    tag: &[u8],
-    ad: &[u8],
-    key: &[u8],
-    nonce: &[u8],
-) -> Result<(), Box<dyn Error + 'static>> {
+pub fn chachapoly_open(data: &mut [u8], tag: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<(), Error> {
     // build footer
     let mut foot = Vec::with_capacity(16);
     foot.extend_from_slice(&(ad.len() as u64).to_le_bytes());
@@ -67,7 +61,7 @@ pub fn chachapoly_open(
         ChaCha20Ietf::xor(key, nonce, 1, data);
         Ok(())
     } else {
-        Err(crate::Error::InvalidData.into())
+        Err(crate::Error::AuthenticationFailed)
     }
 }
 
@@ -82,9 +76,13 @@ impl ChaChaPolyIetf {
     pub fn aead_cipher() -> Box<dyn AeadCipher> {
         Box::new(Self)
     }
+    // create a new detached tag AEAD Cipher with ChaChaPolyIETF
+    pub fn detached_aead_cipher() -> Box<dyn DetachedAeadCipher> {
+        Box::new(Self)
+    }
 }
 impl SecretKeyGen for ChaChaPolyIetf {
-    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Box<dyn Error + 'static>> {
+    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Error> {
         verify_keygen!(CHACHAPOLY_KEY => buf);
 
         rng.random(&mut buf[..CHACHAPOLY_KEY])?;
@@ -107,53 +105,22 @@ impl Cipher for ChaChaPolyIetf {
         plain_len + 16
     }
 
-    fn encrypt(
-        &self,
-        buf: &mut [u8],
-        plain_len: usize,
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn encrypt(&self, buf: &mut [u8], plain_len: usize, key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         self.seal(buf, plain_len, &[], key, nonce)
     }
-    fn encrypt_to(
-        &self,
-        buf: &mut [u8],
-        plain: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn encrypt_to(&self, buf: &mut [u8], plain: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         self.seal_with(buf, plain, &[], key, nonce)
     }
 
-    fn decrypt(
-        &self,
-        buf: &mut [u8],
-        cipher_len: usize,
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn decrypt(&self, buf: &mut [u8], cipher_len: usize, key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         self.open(buf, cipher_len, &[], key, nonce)
     }
-    fn decrypt_to(
-        &self,
-        buf: &mut [u8],
-        cipher: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn decrypt_to(&self, buf: &mut [u8], cipher: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         self.open_to(buf, cipher, &[], key, nonce)
     }
 }
 impl AeadCipher for ChaChaPolyIetf {
-    fn seal(
-        &self,
-        buf: &mut [u8],
-        plain_len: usize,
-        ad: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn seal(&self, buf: &mut [u8], plain_len: usize, ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_seal!(
             key => [CHACHAPOLY_KEY], nonce => [CHACHAPOLY_NONCE],
             plain_len => [buf, CHACHAPOLY_MAX]
@@ -163,14 +130,7 @@ impl AeadCipher for ChaChaPolyIetf {
         chachapoly_seal(data, &mut tag[..CHACHAPOLY_TAG], ad, key, nonce);
         Ok(plain_len + CHACHAPOLY_TAG)
     }
-    fn seal_with(
-        &self,
-        buf: &mut [u8],
-        plain: &[u8],
-        ad: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn seal_with(&self, buf: &mut [u8], plain: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_seal!(
             key => [CHACHAPOLY_KEY], nonce => [CHACHAPOLY_NONCE],
             plain => [buf, CHACHAPOLY_MAX]
@@ -182,14 +142,7 @@ impl AeadCipher for ChaChaPolyIetf {
         Ok(plain.len() + CHACHAPOLY_TAG)
     }
 
-    fn open(
-        &self,
-        buf: &mut [u8],
-        cipher_len: usize,
-        ad: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn open(&self, buf: &mut [u8], cipher_len: usize, ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_open!(
             key => [CHACHAPOLY_KEY], nonce => [CHACHAPOLY_NONCE],
             cipher_len => [buf, CHACHAPOLY_TAG, CHACHAPOLY_MAX]
@@ -199,14 +152,7 @@ impl AeadCipher for ChaChaPolyIetf {
         chachapoly_open(data, &tag[..CHACHAPOLY_TAG], ad, key, nonce)?;
         Ok(cipher_len - CHACHAPOLY_TAG)
     }
-    fn open_to(
-        &self,
-        buf: &mut [u8],
-        cipher: &[u8],
-        ad: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn open_to(&self, buf: &mut [u8], cipher: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_open!(
             key => [CHACHAPOLY_KEY], nonce => [CHACHAPOLY_NONCE],
             cipher => [buf, CHACHAPOLY_TAG, CHACHAPOLY_MAX]
@@ -218,3 +164,31 @@ impl AeadCipher for ChaChaPolyIetf {
         Ok(cipher.len() - CHACHAPOLY_TAG)
     }
 }
+impl DetachedAeadCipher for ChaChaPolyIetf {
+    fn seal_detached(
+        &self,
+        data: &mut [u8],
+        tag: &mut [u8],
+        ad: &[u8],
+        key: &[u8],
+        nonce: &[u8],
+    ) -> Result<usize, Error> {
+        verify_seal_detached!(
+            key => [CHACHAPOLY_KEY], nonce => [CHACHAPOLY_NONCE],
+            data => [CHACHAPOLY_MAX], tag => [CHACHAPOLY_TAG]
+        );
+
+        chachapoly_seal(data, &mut tag[..CHACHAPOLY_TAG], ad, key, nonce);
+        Ok(CHACHAPOLY_TAG)
+    }
+
+    fn open_detached(&self, data: &mut [u8], tag: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        verify_open_detached!(
+            key => [CHACHAPOLY_KEY], nonce => [CHACHAPOLY_NONCE],
+            data => [CHACHAPOLY_MAX], tag => [CHACHAPOLY_TAG]
+        );
+
+        chachapoly_open(data, tag, ad, key, nonce)?;
+        Ok(data.len())
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::ed25519::{ed25519_public_key, ed25519_sign, ed25519_verify};
+use crate::Error;
+use primitives::{
+    rng::{PublicKeyGen, SecretKeyGen, SecureRng},
+    signing::{Sign, SignInfo},
+};
+
+/// size of the secret key (the RFC 8032 seed)
+pub const ED25519_SECRET_KEY: usize = 32;
+/// size of the public key
+pub const ED25519_PUBLIC_KEY: usize = 32;
+/// size of the signature
+pub const ED25519_SIGNATURE: usize = 64;
+
+/// Ed25519 signature scheme (RFC 8032) with strict verification
+pub struct Ed25519;
+impl Ed25519 {
+    /// creates a signer
+    pub fn signer() -> Box<dyn Sign> {
+        Box::new(Self)
+    }
+}
+
+impl SecretKeyGen for Ed25519 {
+    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Error> {
+        verify_keygen!(ED25519_SECRET_KEY => buf);
+
+        rng.random(&mut buf[..ED25519_SECRET_KEY])?;
+        Ok(ED25519_SECRET_KEY)
+    }
+}
+
+impl PublicKeyGen for Ed25519 {
+    fn get_pub_key(&self, buf: &mut [u8], secret_key: &[u8]) -> Result<usize, Error> {
+        verify_sign!(secret_key => [ED25519_SECRET_KEY], => [buf, ED25519_PUBLIC_KEY]);
+
+        ed25519_public_key(buf, secret_key);
+        Ok(ED25519_PUBLIC_KEY)
+    }
+}
+
+impl Sign for Ed25519 {
+    fn info(&self) -> SignInfo {
+        SignInfo {
+            id: "Ed25519",
+            sig_lens: ED25519_SIGNATURE..ED25519_SIGNATURE,
+            secret_key_lens: ED25519_SECRET_KEY..ED25519_SECRET_KEY,
+            public_key_lens: ED25519_PUBLIC_KEY..ED25519_PUBLIC_KEY,
+        }
+    }
+
+    fn sign(&self, buf: &mut [u8], data: &[u8], secret_key: &[u8]) -> Result<usize, Error> {
+        verify_sign!(secret_key => [ED25519_SECRET_KEY], => [buf, ED25519_SIGNATURE]);
+
+        ed25519_sign(buf, data, secret_key);
+        Ok(ED25519_SIGNATURE)
+    }
+
+    /// verifies the signature and rejects non canonical encodings, small order points and unreduced scalars.
+    fn verify(&self, data: &[u8], sig: &[u8], public_key: &[u8]) -> Result<(), Error> {
+        verify_sign!(public_key => [ED25519_PUBLIC_KEY], sig => [ED25519_SIGNATURE]);
+
+        match ed25519_verify(sig, data, public_key) {
+            true => Ok(()),
+            false => Err(crate::Error::AuthenticationFailed),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::Error;
+use crate::{
+    internal::hkdf::{hkdf_sha256_expand, hkdf_sha256_extract, hkdf_sha512_expand, hkdf_sha512_extract},
+    sha2::{SHA256_LEN, SHA512_LEN},
+};
+use primitives::key_derv_func::{KeyDervFunc, KeyDervFuncInfo};
+
+/// min size of the derived output
+pub const HKDF_OUTPUT_MIN: usize = 1;
+/// max size of any key, salt or info
+pub const HKDF_INPUT_MAX: usize = usize::MAX;
+/// size of the HKDF-SHA256 pseudorandom key
+pub const HKDF_SHA256_PRK: usize = SHA256_LEN;
+/// max size of the HKDF-SHA256 output
+pub const HKDF_SHA256_OUTPUT_MAX: usize = 255 * SHA256_LEN;
+/// size of the HKDF-SHA512 pseudorandom key
+pub const HKDF_SHA512_PRK: usize = SHA512_LEN;
+/// max size of the HKDF-SHA512 output
+pub const HKDF_SHA512_OUTPUT_MAX: usize = 255 * SHA512_LEN;
+
+/// HKDF-SHA256 key derivation function (RFC 5869)
+pub struct HkdfSha256;
+impl HkdfSha256 {
+    /// creates a KDF
+    pub fn key_derv_func() -> Box<dyn KeyDervFunc> {
+        Box::new(Self)
+    }
+
+    /// extracts a pseudorandom key from the input key material `ikm` and an optional `salt` into `prk`. Returns
+    /// the length of the pseudorandom key.
+    pub fn extract(&self, prk: &mut [u8], salt: &[u8], ikm: &[u8]) -> Result<usize, Error> {
+        verify_kdf!(=> [prk, HKDF_SHA256_PRK]);
+
+        hkdf_sha256_extract(prk, salt, ikm);
+        Ok(HKDF_SHA256_PRK)
+    }
+
+    /// expands the pseudorandom key `prk` with the context `info` into `buf`.
+    pub fn expand(&self, buf: &mut [u8], prk: &[u8], info: &[u8]) -> Result<(), Error> {
+        verify_kdf!(prk => [>= HKDF_SHA256_PRK], => [buf, HKDF_OUTPUT_MIN, HKDF_SHA256_OUTPUT_MAX]);
+
+        hkdf_sha256_expand(buf, prk, info);
+        Ok(())
+    }
+}
+
+impl KeyDervFunc for HkdfSha256 {
+    fn info(&self) -> KeyDervFuncInfo {
+        KeyDervFuncInfo {
+            id: "HkdfSha256",
+            output_lens: HKDF_OUTPUT_MIN..HKDF_SHA256_OUTPUT_MAX,
+            key_lens: 0..HKDF_INPUT_MAX,
+            salt_lens: 0..HKDF_INPUT_MAX,
+            info_lens: 0..HKDF_INPUT_MAX,
+        }
+    }
+
+    fn derive(&self, buf: &mut [u8], base_key: &[u8], salt: &[u8], info: &[u8]) -> Result<(), Error> {
+        verify_kdf!(=> [buf, HKDF_OUTPUT_MIN, HKDF_SHA256_OUTPUT_MAX]);
+
+        let mut prk = [0; HKDF_SHA256_PRK];
+        hkdf_sha256_extract(&mut prk, salt, base_key);
+        hkdf_sha256_expand(buf, &prk, info);
+        Ok(())
+    }
+}
+
+/// HKDF-SHA512 key derivation function (RFC 5869)
+pub struct HkdfSha512;
+impl HkdfSha512 {
+    /// creates a KDF
+    pub fn key_derv_func() -> Box<dyn KeyDervFunc> {
+        Box::new(Self)
+    }
+
+    /// extracts a pseudorandom key from the input key material `ikm` and an optional `salt` into `prk`. Returns
+    /// the length of the pseudorandom key.
+    pub fn extract(&self, prk: &mut [u8], salt: &[u8], ikm: &[u8]) -> Result<usize, Error> {
+        verify_kdf!(=> [prk, HKDF_SHA512_PRK]);
+
+        hkdf_sha512_extract(prk, salt, ikm);
+        Ok(HKDF_SHA512_PRK)
+    }
+
+    /// expands the pseudorandom key `prk` with the context `info` into `buf`.
+    pub fn expand(&self, buf: &mut [u8], prk: &[u8], info: &[u8]) -> Result<(), Error> {
+        verify_kdf!(prk => [>= HKDF_SHA512_PRK], => [buf, HKDF_OUTPUT_MIN, HKDF_SHA512_OUTPUT_MAX]);
+
+        hkdf_sha512_expand(buf, prk, info);
+        Ok(())
+    }
+}
+
+impl KeyDervFunc for HkdfSha512 {
+    fn info(&self) -> KeyDervFuncInfo {
+        KeyDervFuncInfo {
+            id: "HkdfSha512",
+            output_lens: HKDF_OUTPUT_MIN..HKDF_SHA512_OUTPUT_MAX,
+            key_lens: 0..HKDF_INPUT_MAX,
+            salt_lens: 0..HKDF_INPUT_MAX,
+            info_lens: 0..HKDF_INPUT_MAX,
+        }
+    }
+
+    fn derive(&self, buf: &mut [u8], base_key: &[u8], salt: &[u8], info: &[u8]) -> Result<(), Error> {
+        verify_kdf!(=> [buf, HKDF_OUTPUT_MIN, HKDF_SHA512_OUTPUT_MAX]);
+
+        let mut prk = [0; HKDF_SHA512_PRK];
+        hkdf_sha512_extract(&mut prk, salt, base_key);
+        hkdf_sha512_expand(buf, &prk, info);
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::Error;
+use crate::{
+    internal::hmac::{HmacSha256State, HmacSha512State},
+    sha2::{SHA256_LEN, SHA512_LEN},
+};
+use primitives::{
+    auth::{MacState, MessageAuthCode, MessageAuthCodeInfo, VarLenMessageAuthCode},
+    rng::{SecretKeyGen, SecureRng},
+};
+
+/// min size of the key
+pub const HMAC_KEY_MIN: usize = 0;
+/// max size of the key
+pub const HMAC_KEY_MAX: usize = usize::MAX;
+/// size of the HMAC-SHA256 tag
+pub const HMAC_SHA256_LEN: usize = SHA256_LEN;
+/// min size of a truncated HMAC-SHA256 tag (RFC 2104: at least half the hash length)
+pub const HMAC_SHA256_LEN_MIN: usize = SHA256_LEN / 2;
+/// size of a generated HMAC-SHA256 key
+pub const HMAC_SHA256_KEY: usize = SHA256_LEN;
+/// size of the HMAC-SHA512 tag
+pub const HMAC_SHA512_LEN: usize = SHA512_LEN;
+/// min size of a truncated HMAC-SHA512 tag (RFC 2104: at least half the hash length)
+pub const HMAC_SHA512_LEN_MIN: usize = SHA512_LEN / 2;
+/// size of a generated HMAC-SHA512 key
+pub const HMAC_SHA512_KEY: usize = SHA512_LEN;
+
+/// HMAC-SHA256 message authentication code
+pub struct HmacSha256;
+impl HmacSha256 {
+    /// creates a MAC
+    pub fn message_auth_code() -> Box<dyn MessageAuthCode> {
+        Box::new(Self)
+    }
+
+    /// creates a MAC with truncated tags
+    pub fn var_len_message_auth_code() -> Box<dyn VarLenMessageAuthCode> {
+        Box::new(Self)
+    }
+
+    /// creates an incremental MAC using the `key`
+    pub fn mac_state(key: &[u8]) -> Result<Box<dyn MacState>, Error> {
+        Ok(Box::new(HmacSha256MacState(Some(HmacSha256State::new(key)))))
+    }
+
+    /// verifies a full length or truncated `tag` over the `data` using the `key` in constant time.
+    pub fn verify(&self, tag: &[u8], data: &[u8], key: &[u8]) -> Result<(), Error> {
+        verify_auth!(=> [tag, HMAC_SHA256_LEN_MIN, HMAC_SHA256_LEN]);
+
+        let mut vfy_tag = [0; HMAC_SHA256_LEN];
+        let mut state = HmacSha256State::new(key);
+        state.update(data);
+        state.finish(&mut vfy_tag);
+
+        if eq_const_time!(&tag, &vfy_tag[..tag.len()]) {
+            Ok(())
+        } else {
+            Err(crate::Error::AuthenticationFailed)
+        }
+    }
+}
+
+impl SecretKeyGen for HmacSha256 {
+    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Error> {
+        verify_keygen!(HMAC_SHA256_KEY => buf);
+
+        rng.random(&mut buf[..HMAC_SHA256_KEY])?;
+        Ok(HMAC_SHA256_KEY)
+    }
+}
+
+impl MessageAuthCode for HmacSha256 {
+    fn info(&self) -> MessageAuthCodeInfo {
+        MessageAuthCodeInfo {
+            id: "HmacSha256",
+            one_time: false,
+            len: HMAC_SHA256_LEN,
+            mac_lens: HMAC_SHA256_LEN_MIN..HMAC_SHA256_LEN,
+            key_lens: HMAC_KEY_MIN..HMAC_KEY_MAX,
+        }
+    }
+
+    fn auth(&self, buf: &mut [u8], data: &[u8], key: &[u8]) -> Result<usize, Error> {
+        verify_auth!(=> [buf, HMAC_SHA256_LEN]);
+
+        let mut state = HmacSha256State::new(key);
+        state.update(data);
+        state.finish(&mut buf[..HMAC_SHA256_LEN]);
+        Ok(HMAC_SHA256_LEN)
+    }
+}
+
+impl VarLenMessageAuthCode for HmacSha256 {
+    /// authenticates the data into a tag truncated to `buf.len()` bytes.
+    fn varlen_auth(&self, buf: &mut [u8], data: &[u8], key: &[u8]) -> Result<usize, Error> {
+        verify_auth!(=> [buf, HMAC_SHA256_LEN_MIN, HMAC_SHA256_LEN]);
+
+        let mut tag = [0; HMAC_SHA256_LEN];
+        let mut state = HmacSha256State::new(key);
+        state.update(data);
+        state.finish(&mut tag);
+
+        let len = buf.len();
+        buf.copy_from_slice(&tag[..len]);
+        Ok(len)
+    }
+}
+
+/// HMAC-SHA512 message authentication code
+pub struct HmacSha512;
+impl HmacSha512 {
+    /// creates a MAC
+    pub fn message_auth_code() -> Box<dyn MessageAuthCode> {
+        Box::new(Self)
+    }
+
+    /// creates a MAC with truncated tags
+    pub fn var_len_message_auth_code() -> Box<dyn VarLenMessageAuthCode> {
+        Box::new(Self)
+    }
+
+    /// creates an incremental MAC using the `key`
+    pub fn mac_state(key: &[u8]) -> Result<Box<dyn MacState>, Error> {
+        Ok(Box::new(HmacSha512MacState(Some(HmacSha512State::new(key)))))
+    }
+
+    /// verifies a full length or truncated `tag` over the `data` using the `key` in constant time.
+    pub fn verify(&self, tag: &[u8], data: &[u8], key: &[u8]) -> Result<(), Error> {
+        verify_auth!(=> [tag, HMAC_SHA512_LEN_MIN, HMAC_SHA512_LEN]);
+
+        let mut vfy_tag = [0; HMAC_SHA512_LEN];
+        let mut state = HmacSha512State::new(key);
+        state.update(data);
+        state.finish(&mut vfy_tag);
+
+        if eq_const_time!(&tag, &vfy_tag[..tag.len()]) {
+            Ok(())
+        } else {
+            Err(crate::Error::AuthenticationFailed)
+        }
+    }
+}
+
+impl SecretKeyGen for HmacSha512 {
+    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Error> {
+        verify_keygen!(HMAC_SHA512_KEY => buf);
+
+        rng.random(&mut buf[..HMAC_SHA512_KEY])?;
+        Ok(HMAC_SHA512_KEY)
+    }
+}
+
+impl MessageAuthCode for HmacSha512 {
+    fn info(&self) -> MessageAuthCodeInfo {
+        MessageAuthCodeInfo {
+            id: "HmacSha512",
+            one_time: false,
+            len: HMAC_SHA512_LEN,
+            mac_lens: HMAC_SHA512_LEN_MIN..HMAC_SHA512_LEN,
+            key_lens: HMAC_KEY_MIN..HMAC_KEY_MAX,
+        }
+    }
+
+    fn auth(&self, buf: &mut [u8], data: &[u8], key: &[u8]) -> Result<usize, Error> {
+        verify_auth!(=> [buf, HMAC_SHA512_LEN]);
+
+        let mut state = HmacSha512State::new(key);
+        state.update(data);
+        state.finish(&mut buf[..HMAC_SHA512_LEN]);
+        Ok(HMAC_SHA512_LEN)
+    }
+}
+
+impl VarLenMessageAuthCode for HmacSha512 {
+    /// authenticates the data into a tag truncated to `buf.len()` bytes.
+    fn varlen_auth(&self, buf: &mut [u8], data: &[u8], key: &[u8]) -> Result<usize, Error> {
+        verify_auth!(=> [buf, HMAC_SHA512_LEN_MIN, HMAC_SHA512_LEN]);
+
+        let mut tag = [0; HMAC_SHA512_LEN];
+        let mut state = HmacSha512State::new(key);
+        state.update(data);
+        state.finish(&mut tag);
+
+        let len = buf.len();
+        buf.copy_from_slice(&tag[..len]);
+        Ok(len)
+    }
+}
+
+/// incremental HMAC-SHA256
+struct HmacSha256MacState(Option<HmacSha256State>);
+
+impl MacState for HmacSha256MacState {
+    fn info(&self) -> MessageAuthCodeInfo {
+        MessageAuthCode::info(&HmacSha256)
+    }
+
+    fn update(&mut self, data: &[u8]) -> Result<(), Error> {
+        verify_state!(self.0.as_mut()).update(data);
+        Ok(())
+    }
+
+    fn finalize(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        verify_hash!(=> [buf, HMAC_SHA256_LEN]);
+
+        verify_state!(self.0.take()).finish(&mut buf[..HMAC_SHA256_LEN]);
+        Ok(HMAC_SHA256_LEN)
+    }
+}
+
+/// incremental HMAC-SHA512
+struct HmacSha512MacState(Option<HmacSha512State>);
+
+impl MacState for HmacSha512MacState {
+    fn info(&self) -> MessageAuthCodeInfo {
+        MessageAuthCode::info(&HmacSha512)
+    }
+
+    fn update(&mut self, data: &[u8]) -> Result<(), Error> {
+        verify_state!(self.0.as_mut()).update(data);
+        Ok(())
+    }
+
+    fn finalize(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        verify_hash!(=> [buf, HMAC_SHA512_LEN]);
+
+        verify_state!(self.0.take()).finish(&mut buf[..HMAC_SHA512_LEN]);
+        Ok(HMAC_SHA512_LEN)
+    }
+}
@@ -150,19 +150,83 @@ macro_rules! write32_little_endian {
     }};
 }
 
+/// Little Endian Decode macro u64
+#[macro_export]
+macro_rules! read64_little_endian {
+    ($data:expr) => {{
+        or!(
+            read32_little_endian!(&$data[0..]) as u64,
+            shift_left!(read32_little_endian!(&$data[4..]) as u64, 32)
+        )
+    }};
+}
+
 /// Little Endian Encode macro u64
 #[macro_export]
 macro_rules! write64_little_endian {
 	($num:expr => $data:expr) => ({
-		write32_le!(shift_right!($num,  0) => &mut $data[0..]);
-		write32_le!(shift_right!($num, 32) => &mut $data[4..]);
+		write32_little_endian!(shift_right!($num,  0) => &mut $data[0..]);
+		write32_little_endian!(shift_right!($num, 32) => &mut $data[4..]);
 	});
 }
 
+/// Big Endian Decode macro u32
+#[macro_export]
+macro_rules! read32_big_endian {
+    ($data:expr) => {{
+        or!(
+            shift_left!($data[0] as u32, 24),
+            shift_left!($data[1] as u32, 16),
+            shift_left!($data[2] as u32, 8),
+            shift_left!($data[3] as u32, 0)
+        )
+    }};
+}
+
+/// Big Endian Encode macro u32
+#[macro_export]
+macro_rules! write32_big_endian {
+    ($num:expr => $data:expr) => {{
+        $data[0] = shift_right!($num, 24) as u8;
+        $data[1] = shift_right!($num, 16) as u8;
+        $data[2] = shift_right!($num, 8) as u8;
+        $data[3] = shift_right!($num, 0) as u8;
+    }};
+}
+
+/// Big Endian Decode macro u64
+#[macro_export]
+macro_rules! read64_big_endian {
+    ($data:expr) => {{
+        or!(
+            shift_left!(read32_big_endian!(&$data[0..]) as u64, 32),
+            read32_big_endian!(&$data[4..]) as u64
+        )
+    }};
+}
+
+/// Big Endian Encode macro u64
+#[macro_export]
+macro_rules! write64_big_endian {
+    ($num:expr => $data:expr) => {{
+        write32_big_endian!(shift_right!($num, 32) as u32 => &mut $data[0..]);
+        write32_big_endian!($num as u32 => &mut $data[4..]);
+    }};
+}
+
+/// bit rotation right macro
+#[macro_export]
+macro_rules! rotate_right {
+    ($a:expr, $b:expr) => {{
+        $a.rotate_right($b)
+    }};
+}
+
 /// constant time comparison macro
 #[macro_export]
 macro_rules! eq_const_time {
     ($a:expr, $b:expr) => {{
+        #[allow(unused_imports)]
         use crate::{or, xor};
         if $a.len() == $b.len() {
             let mut x = 0;
@@ -185,5 +249,26 @@ macro_rules! split64_little_endian {
     }};
 }
 
+pub mod aes;
+pub mod aesgcm;
+pub mod aesgcmsiv;
+#[cfg(target_arch = "x86_64")]
+pub mod aesni;
+pub mod argon2;
+pub mod blake2b;
 pub mod chacha;
+#[cfg(target_arch = "x86_64")]
+pub mod chacha_simd;
+pub mod curve25519;
+pub mod ed25519;
+pub mod ghash;
+pub mod hkdf;
+pub mod hmac;
+pub mod pbkdf2;
 pub mod poly;
+#[cfg(any(test, target_pointer_width = "64"))]
+pub mod poly64;
+pub mod polyval;
+pub mod secp256k1;
+pub mod sha2;
+pub mod x25519;
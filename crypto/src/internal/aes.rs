@@ -0,0 +1,305 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+/// number of AES-256 rounds
+const AES256_ROUNDS: usize = 14;
+/// round constants of the key schedule
+const RCON: [u32; 7] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40];
+
+/// exchanges the bits of `x` selected by the `hi` mask with the bits of `y` selected by the `lo` mask
+macro_rules! swap_bits {
+    ($x:expr, $y:expr, $lo:expr, $hi:expr, $s:expr) => {{
+        let (a, b) = ($x, $y);
+        $x = or!(and!(a, $lo), shift_left!(and!(b, $lo), $s));
+        $y = or!(shift_right!(and!(a, $hi), $s), and!(b, $hi));
+    }};
+}
+
+/// transposes the words between the interleaved and the bitsliced representation. Applying it twice is the identity.
+fn ortho(q: &mut [u64; 8]) {
+    for i in (0..8).step_by(2) {
+        swap_bits!(q[i], q[i + 1], 0x5555555555555555, 0xAAAAAAAAAAAAAAAA, 1);
+    }
+    for &i in [0, 1, 4, 5].iter() {
+        swap_bits!(q[i], q[i + 2], 0x3333333333333333, 0xCCCCCCCCCCCCCCCC, 2);
+    }
+    for i in 0..4 {
+        swap_bits!(q[i], q[i + 4], 0x0F0F0F0F0F0F0F0F, 0xF0F0F0F0F0F0F0F0, 4);
+    }
+}
+
+/// spreads the four little endian words of a block into two interleaved words
+fn interleave_in(w: &[u32]) -> (u64, u64) {
+    let mut x = [0u64; 4];
+    (0..4).for_each(|i| {
+        x[i] = w[i] as u64;
+        x[i] = and!(or!(x[i], shift_left!(x[i], 16)), 0x0000FFFF0000FFFF);
+        x[i] = and!(or!(x[i], shift_left!(x[i], 8)), 0x00FF00FF00FF00FF);
+    });
+    (or!(x[0], shift_left!(x[2], 8)), or!(x[1], shift_left!(x[3], 8)))
+}
+
+/// collects the four little endian words of a block from two interleaved words
+fn interleave_out(w: &mut [u32], q0: u64, q1: u64) {
+    let mut x = [
+        and!(q0, 0x00FF00FF00FF00FF),
+        and!(q1, 0x00FF00FF00FF00FF),
+        and!(shift_right!(q0, 8), 0x00FF00FF00FF00FF),
+        and!(shift_right!(q1, 8), 0x00FF00FF00FF00FF),
+    ];
+    (0..4).for_each(|i| {
+        x[i] = and!(or!(x[i], shift_right!(x[i], 8)), 0x0000FFFF0000FFFF);
+        x[i] = or!(x[i], shift_right!(x[i], 16));
+        w[i] = x[i] as u32;
+    });
+}
+
+/// applies the AES S-box to all bytes of the bitsliced state using the Boyar-Peralta circuit
+fn sub_bytes(q: &mut [u64; 8]) {
+    let (x0, x1, x2, x3, x4, x5, x6, x7) = (q[7], q[6], q[5], q[4], q[3], q[2], q[1], q[0]);
+
+    // top linear transformation
+    let y14 = xor!(x3, x5);
+    let y13 = xor!(x0, x6);
+    let y9 = xor!(x0, x3);
+    let y8 = xor!(x0, x5);
+    let t0 = xor!(x1, x2);
+    let y1 = xor!(t0, x7);
+    let y4 = xor!(y1, x3);
+    let y12 = xor!(y13, y14);
+    let y2 = xor!(y1, x0);
+    let y5 = xor!(y1, x6);
+    let y3 = xor!(y5, y8);
+    let t1 = xor!(x4, y12);
+    let y15 = xor!(t1, x5);
+    let y20 = xor!(t1, x1);
+    let y6 = xor!(y15, x7);
+    let y10 = xor!(y15, t0);
+    let y11 = xor!(y20, y9);
+    let y7 = xor!(x7, y11);
+    let y17 = xor!(y10, y11);
+    let y19 = xor!(y10, y8);
+    let y16 = xor!(t0, y11);
+    let y21 = xor!(y13, y16);
+    let y18 = xor!(x0, y16);
+
+    // non-linear section
+    let t2 = and!(y12, y15);
+    let t3 = and!(y3, y6);
+    let t4 = xor!(t3, t2);
+    let t5 = and!(y4, x7);
+    let t6 = xor!(t5, t2);
+    let t7 = and!(y13, y16);
+    let t8 = and!(y5, y1);
+    let t9 = xor!(t8, t7);
+    let t10 = and!(y2, y7);
+    let t11 = xor!(t10, t7);
+    let t12 = and!(y9, y11);
+    let t13 = and!(y14, y17);
+    let t14 = xor!(t13, t12);
+    let t15 = and!(y8, y10);
+    let t16 = xor!(t15, t12);
+    let t17 = xor!(t4, t14);
+    let t18 = xor!(t6, t16);
+    let t19 = xor!(t9, t14);
+    let t20 = xor!(t11, t16);
+    let t21 = xor!(t17, y20);
+    let t22 = xor!(t18, y19);
+    let t23 = xor!(t19, y21);
+    let t24 = xor!(t20, y18);
+
+    let t25 = xor!(t21, t22);
+    let t26 = and!(t21, t23);
+    let t27 = xor!(t24, t26);
+    let t28 = and!(t25, t27);
+    let t29 = xor!(t28, t22);
+    let t30 = xor!(t23, t24);
+    let t31 = xor!(t22, t26);
+    let t32 = and!(t31, t30);
+    let t33 = xor!(t32, t24);
+    let t34 = xor!(t23, t33);
+    let t35 = xor!(t27, t33);
+    let t36 = and!(t24, t35);
+    let t37 = xor!(t36, t34);
+    let t38 = xor!(t27, t36);
+    let t39 = and!(t29, t38);
+    let t40 = xor!(t25, t39);
+
+    let t41 = xor!(t40, t37);
+    let t42 = xor!(t29, t33);
+    let t43 = xor!(t29, t40);
+    let t44 = xor!(t33, t37);
+    let t45 = xor!(t42, t41);
+    let z0 = and!(t44, y15);
+    let z1 = and!(t37, y6);
+    let z2 = and!(t33, x7);
+    let z3 = and!(t43, y16);
+    let z4 = and!(t40, y1);
+    let z5 = and!(t29, y7);
+    let z6 = and!(t42, y11);
+    let z7 = and!(t45, y17);
+    let z8 = and!(t41, y10);
+    let z9 = and!(t44, y12);
+    let z10 = and!(t37, y3);
+    let z11 = and!(t33, y4);
+    let z12 = and!(t43, y13);
+    let z13 = and!(t40, y5);
+    let z14 = and!(t29, y2);
+    let z15 = and!(t42, y9);
+    let z16 = and!(t45, y14);
+    let z17 = and!(t41, y8);
+
+    // bottom linear transformation
+    let t46 = xor!(z15, z16);
+    let t47 = xor!(z10, z11);
+    let t48 = xor!(z5, z13);
+    let t49 = xor!(z9, z10);
+    let t50 = xor!(z2, z12);
+    let t51 = xor!(z2, z5);
+    let t52 = xor!(z7, z8);
+    let t53 = xor!(z0, z3);
+    let t54 = xor!(z6, z7);
+    let t55 = xor!(z16, z17);
+    let t56 = xor!(z12, t48);
+    let t57 = xor!(t50, t53);
+    let t58 = xor!(z4, t46);
+    let t59 = xor!(z3, t54);
+    let t60 = xor!(t46, t57);
+    let t61 = xor!(z14, t57);
+    let t62 = xor!(t52, t58);
+    let t63 = xor!(t49, t58);
+    let t64 = xor!(z4, t59);
+    let t65 = xor!(t61, t62);
+    let t66 = xor!(z1, t63);
+    let s0 = xor!(t59, t63);
+    let s6 = xor!(t56, !t62);
+    let s7 = xor!(t48, !t60);
+    let t67 = xor!(t64, t65);
+    let s3 = xor!(t53, t66);
+    let s4 = xor!(t51, t66);
+    let s5 = xor!(t47, t65);
+    let s1 = xor!(t64, !s3);
+    let s2 = xor!(t55, !t67);
+
+    *q = [s7, s6, s5, s4, s3, s2, s1, s0];
+}
+
+/// rotates the rows of the bitsliced state
+fn shift_rows(q: &mut [u64; 8]) {
+    q.iter_mut().for_each(|x| {
+        let rows = [
+            and!(*x, 0x000000000000FFFF),
+            shift_right!(and!(*x, 0x00000000FFF00000), 4),
+            shift_left!(and!(*x, 0x00000000000F0000), 12),
+            shift_right!(and!(*x, 0x0000FF0000000000), 8),
+            shift_left!(and!(*x, 0x000000FF00000000), 8),
+            shift_right!(and!(*x, 0xF000000000000000), 12),
+            shift_left!(and!(*x, 0x0FFF000000000000), 4),
+        ];
+        *x = rows.iter().fold(0, |acc, row| or!(acc, row));
+    });
+}
+
+/// mixes the columns of the bitsliced state
+fn mix_columns(q: &mut [u64; 8]) {
+    let mut r = [0u64; 8];
+    (0..8).for_each(|i| r[i] = rotate_right!(q[i], 16));
+
+    let c = |i: usize| rotate_right!(xor!(q[i], r[i]), 32);
+    *q = [
+        xor!(xor!(q[7], r[7]), xor!(r[0], c(0))),
+        xor!(xor!(xor!(q[0], r[0]), xor!(q[7], r[7])), xor!(r[1], c(1))),
+        xor!(xor!(q[1], r[1]), xor!(r[2], c(2))),
+        xor!(xor!(xor!(q[2], r[2]), xor!(q[7], r[7])), xor!(r[3], c(3))),
+        xor!(xor!(xor!(q[3], r[3]), xor!(q[7], r[7])), xor!(r[4], c(4))),
+        xor!(xor!(q[4], r[4]), xor!(r[5], c(5))),
+        xor!(xor!(q[5], r[5]), xor!(r[6], c(6))),
+        xor!(xor!(q[6], r[6]), xor!(r[7], c(7))),
+    ];
+}
+
+/// xors a round key into the bitsliced state
+fn add_round_key(q: &mut [u64; 8], key: &[u64; 8]) {
+    (0..8).for_each(|i| q[i] = xor!(q[i], key[i]));
+}
+
+/// applies the S-box to the four bytes of a word
+fn sub_word(x: u32) -> u32 {
+    let mut q = [0u64; 8];
+    q[0] = x as u64;
+    ortho(&mut q);
+    sub_bytes(&mut q);
+    ortho(&mut q);
+    q[0] as u32
+}
+
+/// A bitsliced and constant time AES-256 block cipher that encrypts four blocks at once
+#[derive(Clone)]
+pub struct Aes256Soft {
+    round_keys: [[u64; 8]; AES256_ROUNDS + 1],
+}
+
+impl Aes256Soft {
+    /// expands the 32 byte `key` into the bitsliced round keys
+    pub fn new(key: &[u8]) -> Self {
+        let mut w = [0u32; 4 * (AES256_ROUNDS + 1)];
+        (0..8).for_each(|i| w[i] = read32_little_endian!(&key[i * 4..]));
+        for i in 8..w.len() {
+            let mut t = w[i - 1];
+            if i % 8 == 0 {
+                t = xor!(sub_word(rotate_right!(t, 8)), RCON[i / 8 - 1]);
+            } else if i % 8 == 4 {
+                t = sub_word(t);
+            }
+            w[i] = xor!(w[i - 8], t);
+        }
+
+        // every round key is bitsliced as if it was applied to four blocks
+        let mut round_keys = [[0u64; 8]; AES256_ROUNDS + 1];
+        for (round, key) in round_keys.iter_mut().enumerate() {
+            let (q0, q1) = interleave_in(&w[round * 4..]);
+            *key = [q0, q0, q0, q0, q1, q1, q1, q1];
+            ortho(key);
+        }
+        Self { round_keys }
+    }
+
+    /// encrypts the four 16 byte blocks in `blocks`
+    pub fn encrypt4(&self, blocks: &mut [u8; 64]) {
+        let mut q = [0u64; 8];
+        for i in 0..4 {
+            let mut w = [0u32; 4];
+            (0..4).for_each(|j| w[j] = read32_little_endian!(&blocks[i * 16 + j * 4..]));
+            let (q0, q1) = interleave_in(&w);
+            q[i] = q0;
+            q[i + 4] = q1;
+        }
+        ortho(&mut q);
+
+        add_round_key(&mut q, &self.round_keys[0]);
+        for round in 1..AES256_ROUNDS {
+            sub_bytes(&mut q);
+            shift_rows(&mut q);
+            mix_columns(&mut q);
+            add_round_key(&mut q, &self.round_keys[round]);
+        }
+        sub_bytes(&mut q);
+        shift_rows(&mut q);
+        add_round_key(&mut q, &self.round_keys[AES256_ROUNDS]);
+
+        ortho(&mut q);
+        for i in 0..4 {
+            let mut w = [0u32; 4];
+            interleave_out(&mut w, q[i], q[i + 4]);
+            (0..4).for_each(|j| write32_little_endian!(w[j] => &mut blocks[i * 16 + j * 4..]));
+        }
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::{aes::Aes256Soft, ghash::GhashSoft};
+
+use crate::Error;
+
+/// The block cipher and hash used by AES-256-GCM
+pub trait Aes256GcmCore {
+    /// encrypts the four 16 byte blocks in `blocks`
+    fn encrypt4(&self, blocks: &mut [u8; 64]);
+    /// writes the GHASH of the zero padded `ad` and `cipher` followed by their lengths into `tag`
+    fn ghash(&self, tag: &mut [u8], ad: &[u8], cipher: &[u8]);
+}
+
+/// encodes the bit lengths of the associated data and the ciphertext into the last GHASH block
+pub fn gcm_lengths(ad_len: usize, cipher_len: usize) -> [u8; 16] {
+    let mut block = [0u8; 16];
+    write64_big_endian!(mult!(ad_len as u64, 8) => &mut block[..8]);
+    write64_big_endian!(mult!(cipher_len as u64, 8) => &mut block[8..]);
+    block
+}
+
+/// constant time software AES-256-GCM
+pub struct Aes256GcmSoft {
+    aes: Aes256Soft,
+    h: [u8; 16],
+}
+
+impl Aes256GcmSoft {
+    /// creates the bitsliced key schedule and the hash key
+    pub fn new(key: &[u8]) -> Self {
+        let aes = Aes256Soft::new(key);
+        let mut blocks = [0u8; 64];
+        aes.encrypt4(&mut blocks);
+
+        let mut h = [0u8; 16];
+        h.copy_from_slice(&blocks[..16]);
+        Self { aes, h }
+    }
+}
+
+impl Aes256GcmCore for Aes256GcmSoft {
+    fn encrypt4(&self, blocks: &mut [u8; 64]) {
+        self.aes.encrypt4(blocks);
+    }
+
+    fn ghash(&self, tag: &mut [u8], ad: &[u8], cipher: &[u8]) {
+        let mut ghash = GhashSoft::new(&self.h);
+        ghash.update(ad);
+        ghash.update(cipher);
+        ghash.update(&gcm_lengths(ad.len(), cipher.len()));
+        ghash.finish(tag);
+    }
+}
+
+/// xors the key stream into the data. The counter starts at 2 because 1 is reserved for the tag.
+fn gcm_ctr(core: &impl Aes256GcmCore, nonce: &[u8], data: &mut [u8]) {
+    let mut counter = 2u32;
+    for chunk in data.chunks_mut(64) {
+        let mut blocks = [0u8; 64];
+        for (i, block) in blocks.chunks_exact_mut(16).enumerate() {
+            block[..12].copy_from_slice(nonce);
+            write32_big_endian!(add!(counter, i as u32) => &mut block[12..]);
+        }
+        core.encrypt4(&mut blocks);
+
+        chunk.iter_mut().zip(blocks.iter()).for_each(|(d, k)| *d = xor!(*d, k));
+        counter = add!(counter, 4);
+    }
+}
+
+/// computes the auth tag over the associated data and the ciphertext
+fn gcm_tag(core: &impl Aes256GcmCore, tag: &mut [u8], ad: &[u8], cipher: &[u8], nonce: &[u8]) {
+    let mut blocks = [0u8; 64];
+    blocks[..12].copy_from_slice(nonce);
+    blocks[15] = 1;
+    core.encrypt4(&mut blocks);
+
+    core.ghash(tag, ad, cipher);
+    (0..16).for_each(|i| tag[i] = xor!(tag[i], blocks[i]));
+}
+
+/// encrypts data in place and authenticates it
+pub fn aes256gcm_seal(core: &impl Aes256GcmCore, data: &mut [u8], tag: &mut [u8], ad: &[u8], nonce: &[u8]) {
+    gcm_ctr(core, nonce, data);
+    gcm_tag(core, tag, ad, data, nonce);
+}
+
+/// decrypts data in place after validation
+pub fn aes256gcm_open(
+    core: &impl Aes256GcmCore,
+    data: &mut [u8],
+    tag: &[u8],
+    ad: &[u8],
+    nonce: &[u8],
+) -> Result<(), Error> {
+    let mut vfy_tag = [0u8; 16];
+    gcm_tag(core, &mut vfy_tag, ad, data, nonce);
+
+    if eq_const_time!(&tag, &vfy_tag) {
+        gcm_ctr(core, nonce, data);
+        Ok(())
+    } else {
+        Err(crate::Error::AuthenticationFailed)
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::{aesgcm::Aes256GcmCore, polyval::PolyvalSoft};
+
+use crate::Error;
+
+/// derives the per nonce POLYVAL key and AES-256 key from the key generating key of `core`
+pub fn siv_derive_keys(core: &impl Aes256GcmCore, nonce: &[u8]) -> ([u8; 16], [u8; 32]) {
+    let mut keys = [0u8; 48];
+    for (n, out) in keys.chunks_mut(32).enumerate() {
+        let mut blocks = [0u8; 64];
+        for (i, block) in blocks.chunks_exact_mut(16).enumerate() {
+            write32_little_endian!((n * 4 + i) as u32 => &mut block[..4]);
+            block[4..].copy_from_slice(nonce);
+        }
+        core.encrypt4(&mut blocks);
+
+        // only the first half of every encrypted block is used
+        out.chunks_mut(8)
+            .zip(blocks.chunks(16))
+            .for_each(|(k, b)| k.copy_from_slice(&b[..8]));
+    }
+
+    let (mut auth_key, mut enc_key) = ([0u8; 16], [0u8; 32]);
+    auth_key.copy_from_slice(&keys[..16]);
+    enc_key.copy_from_slice(&keys[16..]);
+    (auth_key, enc_key)
+}
+
+/// xors the key stream into the data. The counter block is the tag with the highest bit set and its first four
+/// bytes are a little endian counter.
+fn siv_ctr(core: &impl Aes256GcmCore, tag: &[u8], data: &mut [u8]) {
+    let mut initial = [0u8; 16];
+    initial.copy_from_slice(tag);
+    initial[15] = or!(initial[15], 0x80);
+    let mut counter = read32_little_endian!(&initial[..4]);
+
+    for chunk in data.chunks_mut(64) {
+        let mut blocks = [0u8; 64];
+        for (i, block) in blocks.chunks_exact_mut(16).enumerate() {
+            block.copy_from_slice(&initial);
+            write32_little_endian!(add!(counter, i as u32) => &mut block[..4]);
+        }
+        core.encrypt4(&mut blocks);
+
+        chunk.iter_mut().zip(blocks.iter()).for_each(|(d, k)| *d = xor!(*d, k));
+        counter = add!(counter, 4);
+    }
+}
+
+/// computes the synthetic IV over the associated data and the plaintext which is also the auth tag
+fn siv_tag(core: &impl Aes256GcmCore, auth_key: &[u8], tag: &mut [u8], ad: &[u8], plain: &[u8], nonce: &[u8]) {
+    let mut lengths = [0u8; 16];
+    write64_little_endian!(mult!(ad.len() as u64, 8) => &mut lengths[..8]);
+    write64_little_endian!(mult!(plain.len() as u64, 8) => &mut lengths[8..]);
+
+    let mut polyval = PolyvalSoft::new(auth_key);
+    polyval.update(ad);
+    polyval.update(plain);
+    polyval.update(&lengths);
+
+    let mut blocks = [0u8; 64];
+    polyval.finish(&mut blocks[..16]);
+    (0..12).for_each(|i| blocks[i] = xor!(blocks[i], nonce[i]));
+    blocks[15] = and!(blocks[15], 0x7f);
+    core.encrypt4(&mut blocks);
+
+    tag.copy_from_slice(&blocks[..16]);
+}
+
+/// encrypts data in place and authenticates it with the derived keys
+pub fn aes256gcmsiv_seal(
+    core: &impl Aes256GcmCore,
+    auth_key: &[u8],
+    data: &mut [u8],
+    tag: &mut [u8],
+    ad: &[u8],
+    nonce: &[u8],
+) {
+    siv_tag(core, auth_key, tag, ad, data, nonce);
+    siv_ctr(core, tag, data);
+}
+
+/// decrypts data in place and validates it with the derived keys. The data is wiped if the validation fails.
+pub fn aes256gcmsiv_open(
+    core: &impl Aes256GcmCore,
+    auth_key: &[u8],
+    data: &mut [u8],
+    tag: &[u8],
+    ad: &[u8],
+    nonce: &[u8],
+) -> Result<(), Error> {
+    siv_ctr(core, tag, data);
+
+    let mut vfy_tag = [0u8; 16];
+    siv_tag(core, auth_key, &mut vfy_tag, ad, data, nonce);
+
+    if eq_const_time!(&tag, &vfy_tag) {
+        Ok(())
+    } else {
+        data.iter_mut().for_each(|b| *b = 0);
+        Err(crate::Error::AuthenticationFailed)
+    }
+}
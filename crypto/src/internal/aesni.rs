@@ -0,0 +1,162 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::aesgcm::{gcm_lengths, Aes256GcmCore};
+
+use std::arch::x86_64::*;
+
+/// AES-256-GCM using the AES-NI and PCLMULQDQ instructions
+#[derive(Clone)]
+pub struct Aes256GcmNi {
+    round_keys: [__m128i; 15],
+    h: __m128i,
+}
+
+impl Aes256GcmNi {
+    /// creates the key schedule and hash key if the CPU supports the required instructions
+    pub fn new(key: &[u8]) -> Option<Self> {
+        if is_x86_feature_detected!("aes") && is_x86_feature_detected!("pclmulqdq") && is_x86_feature_detected!("ssse3")
+        {
+            // safe because the CPU features were detected
+            Some(unsafe { Self::init(key) })
+        } else {
+            None
+        }
+    }
+
+    #[target_feature(enable = "aes,pclmulqdq,ssse3")]
+    unsafe fn init(key: &[u8]) -> Self {
+        let mut round_keys = [_mm_setzero_si128(); 15];
+        round_keys[0] = _mm_loadu_si128(key.as_ptr() as *const __m128i);
+        round_keys[1] = _mm_loadu_si128(key[16..].as_ptr() as *const __m128i);
+
+        // xors every word of the key into the following words
+        let spread = |k: __m128i| {
+            let k = _mm_xor_si128(k, _mm_slli_si128(k, 4));
+            let k = _mm_xor_si128(k, _mm_slli_si128(k, 4));
+            _mm_xor_si128(k, _mm_slli_si128(k, 4))
+        };
+        // the even round keys use the rotated and substituted last word and the odd ones only the substituted word
+        macro_rules! expand {
+            ($i:expr, $rcon:expr) => {{
+                let t = _mm_shuffle_epi32(_mm_aeskeygenassist_si128(round_keys[$i - 1], $rcon), 0xff);
+                round_keys[$i] = _mm_xor_si128(spread(round_keys[$i - 2]), t);
+            }};
+            ($i:expr) => {{
+                let t = _mm_shuffle_epi32(_mm_aeskeygenassist_si128(round_keys[$i - 1], 0x00), 0xaa);
+                round_keys[$i] = _mm_xor_si128(spread(round_keys[$i - 2]), t);
+            }};
+        }
+        expand!(2, 0x01);
+        expand!(3);
+        expand!(4, 0x02);
+        expand!(5);
+        expand!(6, 0x04);
+        expand!(7);
+        expand!(8, 0x08);
+        expand!(9);
+        expand!(10, 0x10);
+        expand!(11);
+        expand!(12, 0x20);
+        expand!(13);
+        expand!(14, 0x40);
+
+        let mut this = Self {
+            round_keys,
+            h: _mm_setzero_si128(),
+        };
+        let mut block = [0u8; 16];
+        this.encrypt(&mut block);
+        this.h = byte_swap(_mm_loadu_si128(block.as_ptr() as *const __m128i));
+        this
+    }
+
+    #[target_feature(enable = "aes,pclmulqdq,ssse3")]
+    unsafe fn encrypt(&self, blocks: &mut [u8]) {
+        for block in blocks.chunks_exact_mut(16) {
+            let mut b = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+            b = _mm_xor_si128(b, self.round_keys[0]);
+            for key in self.round_keys[1..14].iter() {
+                b = _mm_aesenc_si128(b, *key);
+            }
+            b = _mm_aesenclast_si128(b, self.round_keys[14]);
+            _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, b);
+        }
+    }
+
+    #[target_feature(enable = "aes,pclmulqdq,ssse3")]
+    unsafe fn hash(&self, tag: &mut [u8], ad: &[u8], cipher: &[u8]) {
+        let mut y = _mm_setzero_si128();
+        let lengths = gcm_lengths(ad.len(), cipher.len());
+        for data in [ad, cipher, &lengths[..]].iter() {
+            for chunk in data.chunks(16) {
+                let mut block = [0u8; 16];
+                block[..chunk.len()].copy_from_slice(chunk);
+                let x = byte_swap(_mm_loadu_si128(block.as_ptr() as *const __m128i));
+                y = gf_mul(_mm_xor_si128(y, x), self.h);
+            }
+        }
+        _mm_storeu_si128(tag.as_mut_ptr() as *mut __m128i, byte_swap(y));
+    }
+}
+
+impl Aes256GcmCore for Aes256GcmNi {
+    fn encrypt4(&self, blocks: &mut [u8; 64]) {
+        // safe because the instance only exists if the CPU features were detected
+        unsafe { self.encrypt(blocks) }
+    }
+
+    fn ghash(&self, tag: &mut [u8], ad: &[u8], cipher: &[u8]) {
+        // safe because the instance only exists if the CPU features were detected
+        unsafe { self.hash(tag, ad, cipher) }
+    }
+}
+
+/// reverses the byte order of a block
+#[target_feature(enable = "ssse3")]
+unsafe fn byte_swap(x: __m128i) -> __m128i {
+    _mm_shuffle_epi8(x, _mm_set_epi8(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15))
+}
+
+/// multiplies two byte swapped field elements in GF(2^128) and reduces the product
+#[target_feature(enable = "pclmulqdq,sse2")]
+unsafe fn gf_mul(a: __m128i, b: __m128i) -> __m128i {
+    // schoolbook multiplication into the 256 bit product lo:hi
+    let mut lo = _mm_clmulepi64_si128(a, b, 0x00);
+    let mut mid = _mm_xor_si128(_mm_clmulepi64_si128(a, b, 0x10), _mm_clmulepi64_si128(a, b, 0x01));
+    let mut hi = _mm_clmulepi64_si128(a, b, 0x11);
+    lo = _mm_xor_si128(lo, _mm_slli_si128(mid, 8));
+    hi = _mm_xor_si128(hi, _mm_srli_si128(mid, 8));
+
+    // shift the product left by one bit because the operands are bit reflected
+    let lo_carry = _mm_srli_epi32(lo, 31);
+    let hi_carry = _mm_srli_epi32(hi, 31);
+    lo = _mm_slli_epi32(lo, 1);
+    hi = _mm_slli_epi32(hi, 1);
+    hi = _mm_or_si128(hi, _mm_srli_si128(lo_carry, 12));
+    hi = _mm_or_si128(hi, _mm_slli_si128(hi_carry, 4));
+    lo = _mm_or_si128(lo, _mm_slli_si128(lo_carry, 4));
+
+    // reduce modulo x^128 + x^7 + x^2 + x + 1
+    mid = _mm_xor_si128(
+        _mm_xor_si128(_mm_slli_epi32(lo, 31), _mm_slli_epi32(lo, 30)),
+        _mm_slli_epi32(lo, 25),
+    );
+    let carry = _mm_srli_si128(mid, 4);
+    lo = _mm_xor_si128(lo, _mm_slli_si128(mid, 12));
+    let mut fold = _mm_xor_si128(
+        _mm_xor_si128(_mm_srli_epi32(lo, 1), _mm_srli_epi32(lo, 2)),
+        _mm_srli_epi32(lo, 7),
+    );
+    fold = _mm_xor_si128(fold, carry);
+    lo = _mm_xor_si128(lo, fold);
+    _mm_xor_si128(hi, lo)
+}
@@ -0,0 +1,309 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{internal::blake2b::Blake2bState, Error};
+use std::mem::size_of;
+
+/// size of a memory block in 64 bit words
+const BLOCK_WORDS: usize = 128;
+/// number of synchronization points (slices) per pass
+const SYNC_POINTS: usize = 4;
+/// the Argon2 version number
+const VERSION: u32 = 0x13;
+/// the Argon2id type number
+const TYPE_ID: u32 = 2;
+
+/// a 1 KiB memory block
+type Block = [u64; BLOCK_WORDS];
+
+/// absorbs a little endian u32 into the BLAKE2b state
+fn update_le32(state: &mut Blake2bState, n: u32) {
+    let mut b = [0; 4];
+    write32_little_endian!(n => b);
+    state.update(&b);
+}
+
+/// the variable length hash function H' built on top of BLAKE2b
+fn blake2b_long(buf: &mut [u8], inputs: &[&[u8]]) {
+    if buf.len() <= 64 {
+        let mut state = Blake2bState::new(buf.len(), &[]);
+        update_le32(&mut state, buf.len() as u32);
+        inputs.iter().for_each(|i| state.update(i));
+        state.finish(buf);
+        return;
+    }
+
+    // V_1 = H^64(LE32(T) || X)
+    let mut v = [0u8; 64];
+    let mut state = Blake2bState::new(64, &[]);
+    update_le32(&mut state, buf.len() as u32);
+    inputs.iter().for_each(|i| state.update(i));
+    state.finish(&mut v);
+
+    // output the first half of each V_i and hash the remaining bytes into the last V
+    let mut pos = 0;
+    while buf.len() - pos > 64 {
+        buf[pos..pos + 32].copy_from_slice(&v[..32]);
+        pos += 32;
+
+        let out_len = match buf.len() - pos > 64 {
+            true => 64,
+            false => buf.len() - pos,
+        };
+        let mut state = Blake2bState::new(out_len, &[]);
+        state.update(&v);
+        state.finish(&mut v[..out_len]);
+    }
+    let rest = buf.len() - pos;
+    buf[pos..].copy_from_slice(&v[..rest]);
+}
+
+/// the BlaMka permutation applied to sixteen words of a block
+fn permute(b: &mut Block, idx: [usize; 16]) {
+    // macro for the multiplication hardened mixing function
+    macro_rules! mix {
+        ($a:expr, $b:expr, $c:expr, $d:expr) => {{
+            let (a, b, c, d) = (idx[$a], idx[$b], idx[$c], idx[$d]);
+            b_mix!(a, b, c, d, 32, 24);
+            b_mix!(a, b, c, d, 16, 63);
+        }};
+    }
+    macro_rules! b_mix {
+        ($a:expr, $b:expr, $c:expr, $d:expr, $r1:expr, $r2:expr) => {{
+            b[$a] = add!(
+                b[$a],
+                b[$b],
+                shift_left!(mult!(b[$a] as u32 as u64, b[$b] as u32 as u64), 1)
+            );
+            b[$d] = rotate_right!(xor!(b[$d], b[$a]), $r1);
+            b[$c] = add!(
+                b[$c],
+                b[$d],
+                shift_left!(mult!(b[$c] as u32 as u64, b[$d] as u32 as u64), 1)
+            );
+            b[$b] = rotate_right!(xor!(b[$b], b[$c]), $r2);
+        }};
+    }
+
+    mix!(0, 4, 8, 12);
+    mix!(1, 5, 9, 13);
+    mix!(2, 6, 10, 14);
+    mix!(3, 7, 11, 15);
+    mix!(0, 5, 10, 15);
+    mix!(1, 6, 11, 12);
+    mix!(2, 7, 8, 13);
+    mix!(3, 4, 9, 14);
+}
+
+/// the compression function G which writes `G(x, y)` into `out`, or XORs it into `out` when `with_xor` is set
+fn compress(out: &mut Block, x: &Block, y: &Block, with_xor: bool) {
+    let mut r = [0u64; BLOCK_WORDS];
+    (0..BLOCK_WORDS).for_each(|i| r[i] = xor!(x[i], y[i]));
+
+    let mut tmp = r;
+    if with_xor {
+        (0..BLOCK_WORDS).for_each(|i| tmp[i] = xor!(tmp[i], out[i]));
+    }
+
+    // apply the permutation to the rows and then to the columns of the 8x8 matrix of 16 byte registers
+    for row in 0..8 {
+        let mut idx = [0; 16];
+        (0..16).for_each(|i| idx[i] = row * 16 + i);
+        permute(&mut r, idx);
+    }
+    for col in 0..8 {
+        let mut idx = [0; 16];
+        (0..8).for_each(|i| {
+            idx[2 * i] = i * 16 + col * 2;
+            idx[2 * i + 1] = i * 16 + col * 2 + 1;
+        });
+        permute(&mut r, idx);
+    }
+
+    (0..BLOCK_WORDS).for_each(|i| out[i] = xor!(tmp[i], r[i]));
+}
+
+/// decodes a 1 KiB byte string into a block
+fn block_from_bytes(bytes: &[u8]) -> Block {
+    let mut block = [0u64; BLOCK_WORDS];
+    (0..BLOCK_WORDS).for_each(|i| {
+        block[i] = or!(
+            read32_little_endian!(&bytes[i * 8..]) as u64,
+            shift_left!(read32_little_endian!(&bytes[i * 8 + 4..]) as u64, 32)
+        )
+    });
+    block
+}
+
+/// The parameters of an Argon2id computation
+pub struct Argon2Params<'a> {
+    pub password: &'a [u8],
+    pub salt: &'a [u8],
+    pub secret: &'a [u8],
+    pub ad: &'a [u8],
+    /// number of passes
+    pub time_cost: u32,
+    /// memory size in KiB
+    pub memory_cost: u32,
+    /// number of lanes
+    pub parallelism: u32,
+}
+
+/// computes the Argon2id (version 1.3) tag of `buf.len()` bytes. Fails with `InvalidMemoryCost` if the memory exceeds
+/// the address space.
+pub fn argon2id(buf: &mut [u8], params: &Argon2Params) -> Result<(), Error> {
+    let lanes = params.parallelism as usize;
+    let passes = params.time_cost as usize;
+
+    // the memory is rounded down to a multiple of 4 * lanes blocks
+    let segment_len = params.memory_cost as usize / (SYNC_POINTS * lanes);
+    let lane_len = segment_len * SYNC_POINTS;
+    let block_count = lane_len * lanes;
+
+    // an allocation larger than `isize::MAX` bytes panics, so such a memory cost is rejected up front
+    match block_count.checked_mul(size_of::<Block>()) {
+        Some(size) if size <= isize::MAX as usize => (),
+        _ => return Err(Error::InvalidMemoryCost),
+    }
+
+    // H_0 over the parameters and the inputs
+    let mut h0 = [0u8; 72];
+    let mut state = Blake2bState::new(64, &[]);
+    let params_le32 = [
+        params.parallelism,
+        buf.len() as u32,
+        params.memory_cost,
+        params.time_cost,
+        VERSION,
+        TYPE_ID,
+    ];
+    params_le32.iter().for_each(|n| update_le32(&mut state, *n));
+    for input in [params.password, params.salt, params.secret, params.ad].iter() {
+        update_le32(&mut state, input.len() as u32);
+        state.update(input);
+    }
+    state.finish(&mut h0[..64]);
+
+    // the first two blocks of each lane are derived from H_0
+    let mut memory = vec![[0u64; BLOCK_WORDS]; block_count];
+    let mut bytes = [0u8; 1024];
+    for lane in 0..lanes {
+        for i in 0..2 {
+            write32_little_endian!(i as u32 => &mut h0[64..]);
+            write32_little_endian!(lane as u32 => &mut h0[68..]);
+            blake2b_long(&mut bytes, &[&h0]);
+            memory[lane * lane_len + i] = block_from_bytes(&bytes);
+        }
+    }
+
+    let zero = [0u64; BLOCK_WORDS];
+    for pass in 0..passes {
+        for slice in 0..SYNC_POINTS {
+            // lanes only reference blocks of finished slices in other lanes, so they can be filled in sequence
+            for lane in 0..lanes {
+                // the first half of the first pass uses data independent addressing
+                let independent = pass == 0 && slice < SYNC_POINTS / 2;
+                let mut input = [0u64; BLOCK_WORDS];
+                let mut addresses = [0u64; BLOCK_WORDS];
+                if independent {
+                    input[0] = pass as u64;
+                    input[1] = lane as u64;
+                    input[2] = slice as u64;
+                    input[3] = block_count as u64;
+                    input[4] = passes as u64;
+                    input[5] = TYPE_ID as u64;
+                }
+                let next_addresses = |input: &mut Block, addresses: &mut Block| {
+                    input[6] = add!(input[6], 1);
+                    compress(addresses, &zero, input, false);
+                    let tmp = *addresses;
+                    compress(addresses, &zero, &tmp, false);
+                };
+
+                let start = match pass == 0 && slice == 0 {
+                    true => {
+                        if independent {
+                            next_addresses(&mut input, &mut addresses);
+                        }
+                        2
+                    }
+                    false => 0,
+                };
+
+                for index in start..segment_len {
+                    let col = slice * segment_len + index;
+                    let curr = lane * lane_len + col;
+                    let prev = match col {
+                        0 => lane * lane_len + lane_len - 1,
+                        _ => curr - 1,
+                    };
+
+                    let rand = match independent {
+                        true => {
+                            if index % BLOCK_WORDS == 0 {
+                                next_addresses(&mut input, &mut addresses);
+                            }
+                            addresses[index % BLOCK_WORDS]
+                        }
+                        false => memory[prev][0],
+                    };
+
+                    // the reference lane is the current lane in the very first slice
+                    let ref_lane = match pass == 0 && slice == 0 {
+                        true => lane,
+                        false => shift_right!(rand, 32) as usize % lanes,
+                    };
+                    let same_lane = ref_lane == lane;
+
+                    // the blocks that may be referenced (excluding the previous block)
+                    let area = match (pass, same_lane) {
+                        (0, true) => slice * segment_len + index - 1,
+                        (0, false) => slice * segment_len - (index == 0) as usize,
+                        (_, true) => lane_len - segment_len + index - 1,
+                        (_, false) => lane_len - segment_len - (index == 0) as usize,
+                    };
+
+                    // map the pseudo random value non uniformly onto the reference area
+                    let j1 = rand as u32 as u64;
+                    let x = shift_right!(mult!(j1, j1), 32);
+                    let y = shift_right!(mult!(area as u64, x), 32);
+                    let relative = area as u64 - 1 - y;
+                    let start_pos = match pass == 0 || slice == SYNC_POINTS - 1 {
+                        true => 0,
+                        false => (slice + 1) * segment_len,
+                    };
+                    let ref_index = (start_pos + relative as usize) % lane_len;
+                    let reference = ref_lane * lane_len + ref_index;
+
+                    let (prev_block, ref_block) = (memory[prev], memory[reference]);
+                    compress(&mut memory[curr], &prev_block, &ref_block, pass > 0);
+                }
+            }
+        }
+    }
+
+    // XOR the last column and hash it into the tag
+    let mut last = memory[lane_len - 1];
+    for lane in 1..lanes {
+        let block = &memory[lane * lane_len + lane_len - 1];
+        (0..BLOCK_WORDS).for_each(|i| last[i] = xor!(last[i], block[i]));
+    }
+    (0..BLOCK_WORDS).for_each(|i| {
+        write32_little_endian!(last[i] as u32 => &mut bytes[i * 8..]);
+        write32_little_endian!(shift_right!(last[i], 32) as u32 => &mut bytes[i * 8 + 4..]);
+    });
+    blake2b_long(buf, &[&bytes]);
+
+    // wipe the memory
+    memory.iter_mut().for_each(|b| *b = zero);
+    bytes.iter_mut().for_each(|b| *b = 0);
+    Ok(())
+}
@@ -0,0 +1,161 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use std::cmp::min;
+
+/// BLAKE2b initialization vector
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// BLAKE2b message word permutations
+const SIGMA: [[usize; 16]; 12] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+];
+
+/// Size of a BLAKE2b block
+pub const BLAKE2B_BLOCK: usize = 128;
+
+/// compresses a 128 byte block into the state. `t` is the byte counter and `last` marks the final block.
+fn blake2b_compress(h: &mut [u64; 8], block: &[u8], t: u128, last: bool) {
+    // load the message words
+    let mut m = [0u64; 16];
+    (0..16).for_each(|i| {
+        m[i] = or!(
+            read32_little_endian!(&block[i * 8..]) as u64,
+            shift_left!(read32_little_endian!(&block[i * 8 + 4..]) as u64, 32)
+        )
+    });
+
+    // initialize the working vector
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&IV);
+    v[12] = xor!(v[12], t as u64);
+    v[13] = xor!(v[13], shift_right!(t, 64) as u64);
+    if last {
+        v[14] = !v[14];
+    }
+
+    // macro for the mixing function
+    macro_rules! mix {
+        ($a:expr, $b:expr, $c:expr, $d:expr, $x:expr, $y:expr) => {{
+            v[$a] = add!(v[$a], v[$b], $x);
+            v[$d] = rotate_right!(xor!(v[$d], v[$a]), 32);
+            v[$c] = add!(v[$c], v[$d]);
+            v[$b] = rotate_right!(xor!(v[$b], v[$c]), 24);
+            v[$a] = add!(v[$a], v[$b], $y);
+            v[$d] = rotate_right!(xor!(v[$d], v[$a]), 16);
+            v[$c] = add!(v[$c], v[$d]);
+            v[$b] = rotate_right!(xor!(v[$b], v[$c]), 63);
+        }};
+    }
+
+    // twelve rounds
+    for s in SIGMA.iter() {
+        mix!(0, 4, 8, 12, m[s[0]], m[s[1]]);
+        mix!(1, 5, 9, 13, m[s[2]], m[s[3]]);
+        mix!(2, 6, 10, 14, m[s[4]], m[s[5]]);
+        mix!(3, 7, 11, 15, m[s[6]], m[s[7]]);
+        mix!(0, 5, 10, 15, m[s[8]], m[s[9]]);
+        mix!(1, 6, 11, 12, m[s[10]], m[s[11]]);
+        mix!(2, 7, 8, 13, m[s[12]], m[s[13]]);
+        mix!(3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+
+    // fold the working vector into the state
+    (0..8).for_each(|i| h[i] = xor!(h[i], xor!(v[i], v[i + 8])));
+}
+
+/// The running state of a BLAKE2b computation
+#[derive(Clone)]
+pub struct Blake2bState {
+    h: [u64; 8],
+    buf: [u8; BLAKE2B_BLOCK],
+    buf_len: usize,
+    t: u128,
+    out_len: usize,
+}
+
+impl Blake2bState {
+    /// creates a new BLAKE2b state with an output length of 1 to 64 bytes and an optional key of up to 64 bytes.
+    pub fn new(out_len: usize, key: &[u8]) -> Self {
+        let mut h = IV;
+        h[0] = xor!(
+            h[0],
+            or!(or!(0x01010000, shift_left!(key.len() as u64, 8)), out_len as u64)
+        );
+
+        let mut this = Self {
+            h,
+            buf: [0; BLAKE2B_BLOCK],
+            buf_len: 0,
+            t: 0,
+            out_len,
+        };
+
+        // the key is padded to a full block and processed as the first block
+        if !key.is_empty() {
+            this.buf[..key.len()].copy_from_slice(key);
+            this.buf_len = BLAKE2B_BLOCK;
+        }
+        this
+    }
+
+    /// absorbs data into the state
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            // the last block must be compressed with the final flag so only compress when more data follows
+            if self.buf_len == BLAKE2B_BLOCK {
+                self.t = add!(self.t, BLAKE2B_BLOCK as u128);
+                blake2b_compress(&mut self.h, &self.buf, self.t, false);
+                self.buf_len = 0;
+            }
+
+            let to_copy = min(data.len(), BLAKE2B_BLOCK - self.buf_len);
+            self.buf[self.buf_len..self.buf_len + to_copy].copy_from_slice(&data[..to_copy]);
+            self.buf_len += to_copy;
+            data = &data[to_copy..];
+        }
+    }
+
+    /// compresses the final block and writes the `out_len` bytes long digest into `buf`
+    pub fn finish(mut self, buf: &mut [u8]) {
+        self.t = add!(self.t, self.buf_len as u128);
+        self.buf[self.buf_len..].iter_mut().for_each(|b| *b = 0);
+        blake2b_compress(&mut self.h, &self.buf, self.t, true);
+
+        let mut out = [0; 64];
+        (0..8).for_each(|i| {
+            write32_little_endian!(self.h[i] as u32 => &mut out[i * 8..]);
+            write32_little_endian!(shift_right!(self.h[i], 32) as u32 => &mut out[i * 8 + 4..]);
+        });
+        buf[..self.out_len].copy_from_slice(&out[..self.out_len]);
+    }
+}
@@ -9,6 +9,8 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
+use std::cmp::min;
+
 /// ChaCha20 Basis Constants
 const BASIS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
 
@@ -62,44 +64,88 @@ pub fn h_chacha20_hash(key: &[u8], nonce: &[u8], buf: &mut [u8]) {
     (12..16).for_each(|i| write32_little_endian!(state[i] => &mut buf_b[(i - 12) * 4..]));
 }
 
-/// calculates the nth ChaCha20-IETF block into a buffer
-pub fn chacha20_ietf_block(key: &[u8], nonce: &[u8], n: u32, buf: &mut [u8]) {
-    // create buffer
-    let mut state = vec![0u32; 32];
-    let (init, mixed) = state.split_at_mut(16);
-
-    // initialize buffer
-    (0..4).for_each(|i| init[i] = BASIS[i]);
-    (4..12).for_each(|i| init[i] = read32_little_endian!(&key[(i - 4) * 4..]));
-    init[12] = n;
-    (13..16).for_each(|i| init[i] = read32_little_endian!(&nonce[(i - 13) * 4..]));
-
-    // mix the buffer
-    mixed.copy_from_slice(init);
-    chacha20_rounds(mixed);
+/// calculates the block of an initialized state into a buffer
+fn chacha20_state_block(init: &[u32; 16], buf: &mut [u8]) {
+    // mix the state
+    let mut mixed = *init;
+    chacha20_rounds(&mut mixed);
 
     // write the mixed state into the buffer
     (0..16).for_each(|i| mixed[i] = add!(mixed[i], init[i]));
     (0..16).for_each(|i| write32_little_endian!(mixed[i] => &mut buf[i * 4..]));
 }
 
-/// calculates the nth ChaCha20 block into a buffer
-pub fn chacha20_block(key: &[u8], nonce: &[u8], n: u64, buf: &mut [u8]) {
-    // create buffer
-    let mut state = vec![0u32; 32];
-    let (init, mixed) = state.split_at_mut(16);
+/// advances the block counter of the state by `blocks`. If `wide` is set the counter spans two words.
+fn chacha20_advance(state: &mut [u32; 16], wide: bool, blocks: u32) {
+    let counter = add!(state[12], blocks);
+    if wide && counter < state[12] {
+        state[13] = add!(state[13], 1);
+    }
+    state[12] = counter;
+}
 
-    // initialize buffer
-    (0..4).for_each(|i| init[i] = BASIS[i]);
-    (4..12).for_each(|i| init[i] = read32_little_endian!(&key[(i - 4) * 4..]));
-    split64_little_endian!(n => &mut init[12..]);
-    (14..16).for_each(|i| init[i] = read32_little_endian!(&nonce[(i - 14) * 4..]));
+/// xors the key stream of an initialized state into the data. Several blocks are computed at once with AVX2 or SSE2
+/// if the CPU supports them and the remaining blocks are computed one at a time.
+fn chacha20_state_xor(state: &mut [u32; 16], wide: bool, mut data: &mut [u8]) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        use crate::internal::chacha_simd::{chacha20_blocks_avx2, chacha20_blocks_sse2};
 
-    // mix the buffer
-    mixed.copy_from_slice(init);
-    chacha20_rounds(mixed);
+        if is_x86_feature_detected!("avx2") {
+            while data.len() >= 512 {
+                // safe because the CPU feature was detected
+                unsafe { chacha20_blocks_avx2(state, wide, &mut data[..512]) };
+                chacha20_advance(state, wide, 8);
+                data = &mut data[512..];
+            }
+        }
+        if is_x86_feature_detected!("sse2") {
+            while data.len() >= 256 {
+                // safe because the CPU feature was detected
+                unsafe { chacha20_blocks_sse2(state, wide, &mut data[..256]) };
+                chacha20_advance(state, wide, 4);
+                data = &mut data[256..];
+            }
+        }
+    }
 
-    // write the mixed state into the buffer
-    (0..16).for_each(|i| mixed[i] = add!(mixed[i], init[i]));
-    (0..16).for_each(|i| write32_little_endian!(mixed[i] => &mut buf[i * 4..]));
+    let mut buf = [0u8; 64];
+    while !data.is_empty() {
+        chacha20_state_block(state, &mut buf);
+        chacha20_advance(state, wide, 1);
+
+        let to_xor = min(data.len(), buf.len());
+        (0..to_xor).for_each(|i| data[i] = xor!(data[i], buf[i]));
+        data = &mut data[to_xor..];
+    }
+}
+
+/// initializes a ChaCha20-IETF state with a 32 bit counter
+fn chacha20_ietf_state(key: &[u8], nonce: &[u8], n: u32) -> [u32; 16] {
+    let mut state = [0u32; 16];
+    (0..4).for_each(|i| state[i] = BASIS[i]);
+    (4..12).for_each(|i| state[i] = read32_little_endian!(&key[(i - 4) * 4..]));
+    state[12] = n;
+    (13..16).for_each(|i| state[i] = read32_little_endian!(&nonce[(i - 13) * 4..]));
+    state
+}
+
+/// initializes a ChaCha20 state with a 64 bit counter
+fn chacha20_state(key: &[u8], nonce: &[u8], n: u64) -> [u32; 16] {
+    let mut state = [0u32; 16];
+    (0..4).for_each(|i| state[i] = BASIS[i]);
+    (4..12).for_each(|i| state[i] = read32_little_endian!(&key[(i - 4) * 4..]));
+    split64_little_endian!(n => &mut state[12..]);
+    (14..16).for_each(|i| state[i] = read32_little_endian!(&nonce[(i - 14) * 4..]));
+    state
+}
+
+/// xors the ChaCha20-IETF key stream starting at the nth block into the data
+pub fn chacha20_ietf_xor(key: &[u8], nonce: &[u8], n: u32, data: &mut [u8]) {
+    chacha20_state_xor(&mut chacha20_ietf_state(key, nonce, n), false, data);
+}
+
+/// xors the ChaCha20 key stream starting at the nth block into the data
+pub fn chacha20_xor(key: &[u8], nonce: &[u8], n: u64, data: &mut [u8]) {
+    chacha20_state_xor(&mut chacha20_state(key, nonce, n), true, data);
 }
@@ -0,0 +1,151 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use std::arch::x86_64::*;
+
+/// a quarter round over four vectors of state words where every lane belongs to a different block
+macro_rules! simd_quarter_round {
+    ($x:ident, $a:expr, $b:expr, $c:expr, $d:expr, $add:ident, $xor:ident, $rotate:ident) => {{
+        $x[$a] = $add($x[$a], $x[$b]);
+        $x[$d] = $rotate!($xor($x[$d], $x[$a]), 16);
+        $x[$c] = $add($x[$c], $x[$d]);
+        $x[$b] = $rotate!($xor($x[$b], $x[$c]), 12);
+        $x[$a] = $add($x[$a], $x[$b]);
+        $x[$d] = $rotate!($xor($x[$d], $x[$a]), 8);
+        $x[$c] = $add($x[$c], $x[$d]);
+        $x[$b] = $rotate!($xor($x[$b], $x[$c]), 7);
+    }};
+}
+
+/// 32 bit lane rotation with SSE2
+macro_rules! rotate_sse2 {
+    ($v:expr, 16) => {{
+        let v = $v;
+        _mm_or_si128(_mm_slli_epi32(v, 16), _mm_srli_epi32(v, 16))
+    }};
+    ($v:expr, 12) => {{
+        let v = $v;
+        _mm_or_si128(_mm_slli_epi32(v, 12), _mm_srli_epi32(v, 20))
+    }};
+    ($v:expr, 8) => {{
+        let v = $v;
+        _mm_or_si128(_mm_slli_epi32(v, 8), _mm_srli_epi32(v, 24))
+    }};
+    ($v:expr, 7) => {{
+        let v = $v;
+        _mm_or_si128(_mm_slli_epi32(v, 7), _mm_srli_epi32(v, 25))
+    }};
+}
+
+/// 32 bit lane rotation with AVX2. The byte aligned rotations are done with a shuffle.
+macro_rules! rotate_avx2 {
+    ($v:expr, 16) => {{
+        _mm256_shuffle_epi8(
+            $v,
+            _mm256_set_epi8(
+                13, 12, 15, 14, 9, 8, 11, 10, 5, 4, 7, 6, 1, 0, 3, 2, 13, 12, 15, 14, 9, 8, 11, 10, 5, 4, 7, 6, 1, 0,
+                3, 2,
+            ),
+        )
+    }};
+    ($v:expr, 12) => {{
+        let v = $v;
+        _mm256_or_si256(_mm256_slli_epi32(v, 12), _mm256_srli_epi32(v, 20))
+    }};
+    ($v:expr, 8) => {{
+        _mm256_shuffle_epi8(
+            $v,
+            _mm256_set_epi8(
+                14, 13, 12, 15, 10, 9, 8, 11, 6, 5, 4, 7, 2, 1, 0, 3, 14, 13, 12, 15, 10, 9, 8, 11, 6, 5, 4, 7, 2, 1,
+                0, 3,
+            ),
+        )
+    }};
+    ($v:expr, 7) => {{
+        let v = $v;
+        _mm256_or_si256(_mm256_slli_epi32(v, 7), _mm256_srli_epi32(v, 25))
+    }};
+}
+
+/// builds a function that xors the key stream of `$lanes` consecutive blocks into the data. The counter of each block
+/// is computed in advance, so the carry into the 13th word is only propagated if `wide` is set.
+macro_rules! chacha20_blocks {
+    (
+        $name:ident, $feature:literal, $lanes:expr, $vec:ty, $set1:ident, $load:ident, $store:ident, $add:ident,
+        $xor:ident, $rotate:ident
+    ) => {
+        #[target_feature(enable = $feature)]
+        pub unsafe fn $name(init: &[u32; 16], wide: bool, data: &mut [u8]) {
+            // the block counters of the lanes
+            let mut counters = [[0u32; $lanes]; 2];
+            for i in 0..$lanes {
+                counters[0][i] = add!(init[12], i as u32);
+                counters[1][i] = match wide && counters[0][i] < init[12] {
+                    true => add!(init[13], 1),
+                    false => init[13],
+                };
+            }
+
+            // every vector holds one state word of all blocks
+            let mut x = [$set1(0); 16];
+            (0..16).for_each(|i| x[i] = $set1(init[i] as i32));
+            x[12] = $load(counters[0].as_ptr() as *const $vec);
+            x[13] = $load(counters[1].as_ptr() as *const $vec);
+            let start = x;
+
+            for _ in 0..10 {
+                simd_quarter_round!(x, 0, 4, 8, 12, $add, $xor, $rotate);
+                simd_quarter_round!(x, 1, 5, 9, 13, $add, $xor, $rotate);
+                simd_quarter_round!(x, 2, 6, 10, 14, $add, $xor, $rotate);
+                simd_quarter_round!(x, 3, 7, 11, 15, $add, $xor, $rotate);
+                simd_quarter_round!(x, 0, 5, 10, 15, $add, $xor, $rotate);
+                simd_quarter_round!(x, 1, 6, 11, 12, $add, $xor, $rotate);
+                simd_quarter_round!(x, 2, 7, 8, 13, $add, $xor, $rotate);
+                simd_quarter_round!(x, 3, 4, 9, 14, $add, $xor, $rotate);
+            }
+
+            // add the initial state and xor the words of each block into the data
+            let mut words = [[0u32; $lanes]; 16];
+            (0..16).for_each(|i| $store(words[i].as_mut_ptr() as *mut $vec, $add(x[i], start[i])));
+            for (b, block) in data.chunks_exact_mut(64).enumerate() {
+                for (i, word) in block.chunks_exact_mut(4).enumerate() {
+                    let w = xor!(read32_little_endian!(word), words[i][b]);
+                    write32_little_endian!(w => word);
+                }
+            }
+        }
+    };
+}
+
+chacha20_blocks!(
+    chacha20_blocks_sse2,
+    "sse2",
+    4,
+    __m128i,
+    _mm_set1_epi32,
+    _mm_loadu_si128,
+    _mm_storeu_si128,
+    _mm_add_epi32,
+    _mm_xor_si128,
+    rotate_sse2
+);
+chacha20_blocks!(
+    chacha20_blocks_avx2,
+    "avx2",
+    8,
+    __m256i,
+    _mm256_set1_epi32,
+    _mm256_loadu_si256,
+    _mm256_storeu_si256,
+    _mm256_add_epi32,
+    _mm256_xor_si256,
+    rotate_avx2
+);
@@ -0,0 +1,231 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+/// mask of a 51 bit limb
+const MASK51: u64 = (1 << 51) - 1;
+
+/// An element of the field GF(2^255 - 19) in five 51 bit limbs
+#[derive(Clone, Copy, Debug)]
+pub struct Fe(pub [u64; 5]);
+
+impl Fe {
+    /// the additive identity
+    pub const ZERO: Fe = Fe([0, 0, 0, 0, 0]);
+    /// the multiplicative identity
+    pub const ONE: Fe = Fe([1, 0, 0, 0, 0]);
+
+    /// decodes a little endian field element ignoring the top bit
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let load = |i: usize| {
+            or!(
+                read32_little_endian!(&bytes[i..]) as u64,
+                shift_left!(read32_little_endian!(&bytes[i + 4..]) as u64, 32)
+            )
+        };
+        Fe([
+            and!(load(0), MASK51),
+            and!(shift_right!(load(6), 3), MASK51),
+            and!(shift_right!(load(12), 6), MASK51),
+            and!(shift_right!(load(19), 1), MASK51),
+            and!(shift_right!(load(24), 12), MASK51),
+        ])
+    }
+
+    /// encodes the fully reduced field element in little endian
+    pub fn to_bytes(self) -> [u8; 32] {
+        let mut l = self.carry().0;
+
+        // compute q = 1 if the value is at least p and subtract q * p
+        let mut q = shift_right!(add!(l[0], 19), 51);
+        (1..5).for_each(|i| q = shift_right!(add!(l[i], q), 51));
+        l[0] = add!(l[0], mult!(q, 19));
+        (0..4).for_each(|i| {
+            l[i + 1] = add!(l[i + 1], shift_right!(l[i], 51));
+            l[i] = and!(l[i], MASK51);
+        });
+        l[4] = and!(l[4], MASK51);
+
+        let mut out = [0u8; 32];
+        let mut acc: u128 = 0;
+        let mut bits = 0;
+        let mut pos = 0;
+        for limb in l.iter() {
+            acc = or!(acc, shift_left!(*limb as u128, bits));
+            bits += 51;
+            while bits >= 8 && pos < 32 {
+                out[pos] = acc as u8;
+                acc = shift_right!(acc, 8);
+                bits -= 8;
+                pos += 1;
+            }
+        }
+        if pos < 32 {
+            out[pos] = acc as u8;
+        }
+        out
+    }
+
+    /// propagates the carries so that each limb fits into 52 bits
+    fn carry(&self) -> Self {
+        let l = self.0;
+        let mut c = [0u64; 5];
+        (0..5).for_each(|i| c[i] = shift_right!(l[i], 51));
+        Fe([
+            add!(and!(l[0], MASK51), mult!(c[4], 19)),
+            add!(and!(l[1], MASK51), c[0]),
+            add!(and!(l[2], MASK51), c[1]),
+            add!(and!(l[3], MASK51), c[2]),
+            add!(and!(l[4], MASK51), c[3]),
+        ])
+    }
+
+    /// field addition
+    pub fn add(&self, other: &Self) -> Self {
+        let mut l = [0u64; 5];
+        (0..5).for_each(|i| l[i] = add!(self.0[i], other.0[i]));
+        Fe(l).carry()
+    }
+
+    /// field subtraction
+    pub fn sub(&self, other: &Self) -> Self {
+        // add 16 * p to avoid underflows
+        const P16: [u64; 5] = [
+            36028797018963664,
+            36028797018963952,
+            36028797018963952,
+            36028797018963952,
+            36028797018963952,
+        ];
+        let b = other.carry();
+        let mut l = [0u64; 5];
+        (0..5).for_each(|i| l[i] = sub!(add!(self.0[i], P16[i]), b.0[i]));
+        Fe(l).carry()
+    }
+
+    /// field negation
+    pub fn neg(&self) -> Self {
+        Fe::ZERO.sub(self)
+    }
+
+    /// field multiplication
+    pub fn mul(&self, other: &Self) -> Self {
+        let (a, b) = (self.0, other.0);
+        let m = |x: u64, y: u64| mult!(x as u128, y as u128);
+        let mut b19 = [0u64; 5];
+        (0..5).for_each(|i| b19[i] = mult!(b[i], 19));
+
+        let r0 = m(a[0], b[0]) + m(a[1], b19[4]) + m(a[2], b19[3]) + m(a[3], b19[2]) + m(a[4], b19[1]);
+        let r1 = m(a[0], b[1]) + m(a[1], b[0]) + m(a[2], b19[4]) + m(a[3], b19[3]) + m(a[4], b19[2]);
+        let r2 = m(a[0], b[2]) + m(a[1], b[1]) + m(a[2], b[0]) + m(a[3], b19[4]) + m(a[4], b19[3]);
+        let r3 = m(a[0], b[3]) + m(a[1], b[2]) + m(a[2], b[1]) + m(a[3], b[0]) + m(a[4], b19[4]);
+        let r4 = m(a[0], b[4]) + m(a[1], b[3]) + m(a[2], b[2]) + m(a[3], b[1]) + m(a[4], b[0]);
+
+        Self::reduce_wide([r0, r1, r2, r3, r4])
+    }
+
+    /// field squaring
+    pub fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    /// multiplies the field element by a small constant
+    pub fn mul_small(&self, n: u64) -> Self {
+        let mut r = [0u128; 5];
+        (0..5).for_each(|i| r[i] = mult!(self.0[i] as u128, n as u128));
+        Self::reduce_wide(r)
+    }
+
+    /// reduces five 128 bit limbs into a field element
+    fn reduce_wide(mut r: [u128; 5]) -> Self {
+        let mask = MASK51 as u128;
+        (0..4).for_each(|i| {
+            r[i + 1] = add!(r[i + 1], shift_right!(r[i], 51));
+            r[i] = and!(r[i], mask);
+        });
+        let c = shift_right!(r[4], 51);
+        r[4] = and!(r[4], mask);
+        r[0] = add!(r[0], mult!(c, 19));
+        r[1] = add!(r[1], shift_right!(r[0], 51));
+        r[0] = and!(r[0], mask);
+
+        Fe([r[0] as u64, r[1] as u64, r[2] as u64, r[3] as u64, r[4] as u64])
+    }
+
+    /// squares the field element `k` times
+    fn pow2k(&self, k: usize) -> Self {
+        let mut r = *self;
+        (0..k).for_each(|_| r = r.square());
+        r
+    }
+
+    /// computes (self^(2^250 - 1), self^11)
+    fn pow22501(&self) -> (Self, Self) {
+        let t0 = self.square();
+        let t1 = t0.pow2k(2);
+        let t2 = self.mul(&t1);
+        let t3 = t0.mul(&t2);
+        let t4 = t3.square();
+        let t5 = t2.mul(&t4);
+        let t6 = t5.pow2k(5);
+        let t7 = t6.mul(&t5);
+        let t8 = t7.pow2k(10);
+        let t9 = t8.mul(&t7);
+        let t10 = t9.pow2k(20);
+        let t11 = t10.mul(&t9);
+        let t12 = t11.pow2k(10);
+        let t13 = t12.mul(&t7);
+        let t14 = t13.pow2k(50);
+        let t15 = t14.mul(&t13);
+        let t16 = t15.pow2k(100);
+        let t17 = t16.mul(&t15);
+        let t18 = t17.pow2k(50);
+        let t19 = t18.mul(&t13);
+        (t19, t3)
+    }
+
+    /// computes the multiplicative inverse as self^(p - 2)
+    pub fn invert(&self) -> Self {
+        let (t19, t3) = self.pow22501();
+        t19.pow2k(5).mul(&t3)
+    }
+
+    /// computes self^((p - 5) / 8)
+    pub fn pow_p58(&self) -> Self {
+        let (t19, _) = self.pow22501();
+        t19.pow2k(2).mul(self)
+    }
+
+    /// returns whether the field element is zero
+    pub fn is_zero(&self) -> bool {
+        let zero = [0u8; 32];
+        eq_const_time!(&self.to_bytes(), &zero)
+    }
+
+    /// returns whether the field elements are equal
+    pub fn equals(&self, other: &Self) -> bool {
+        eq_const_time!(&self.to_bytes(), &other.to_bytes())
+    }
+
+    /// returns the least significant bit of the reduced field element
+    pub fn is_negative(&self) -> u8 {
+        and!(self.to_bytes()[0], 1)
+    }
+
+    /// swaps `a` and `b` in constant time if `swap` is 1
+    pub fn cswap(a: &mut Self, b: &mut Self, swap: u64) {
+        let mask = negate!(swap);
+        (0..5).for_each(|i| {
+            let t = and!(mask, xor!(a.0[i], b.0[i]));
+            a.0[i] = xor!(a.0[i], t);
+            b.0[i] = xor!(b.0[i], t);
+        });
+    }
+}
@@ -0,0 +1,319 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::{curve25519::Fe, sha2::Sha512State};
+
+/// the curve constant d = -121665 / 121666
+const D: Fe = Fe([
+    929955233495203,
+    466365720129213,
+    1662059464998953,
+    2033849074728123,
+    1442794654840575,
+]);
+/// the curve constant 2 * d
+const D2: Fe = Fe([
+    1859910466990425,
+    932731440258426,
+    1072319116312658,
+    1815898335770999,
+    633789495995903,
+]);
+/// a square root of -1
+const SQRT_M1: Fe = Fe([
+    1718705420411056,
+    234908883556509,
+    2233514472574048,
+    2117202627021982,
+    765476049583133,
+]);
+/// the base point
+const BASE: Point = Point {
+    x: Fe([
+        1738742601995546,
+        1146398526822698,
+        2070867633025821,
+        562264141797630,
+        587772402128613,
+    ]),
+    y: Fe([
+        1801439850948184,
+        1351079888211148,
+        450359962737049,
+        900719925474099,
+        1801439850948198,
+    ]),
+    z: Fe::ONE,
+    t: Fe([
+        1841354044333475,
+        16398895984059,
+        755974180946558,
+        900171276175154,
+        1821297809914039,
+    ]),
+};
+/// the order of the base point L = 2^252 + 27742317777372353535851937790883648493 in little endian
+const L: [i64; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10,
+];
+
+/// A point on the twisted Edwards curve in extended coordinates
+#[derive(Clone, Copy)]
+struct Point {
+    x: Fe,
+    y: Fe,
+    z: Fe,
+    t: Fe,
+}
+
+impl Point {
+    /// the neutral element
+    const IDENTITY: Point = Point {
+        x: Fe::ZERO,
+        y: Fe::ONE,
+        z: Fe::ONE,
+        t: Fe::ZERO,
+    };
+
+    /// adds two points with the complete addition formula, which also works for doubling
+    fn add(&self, other: &Self) -> Self {
+        let a = self.y.sub(&self.x).mul(&other.y.sub(&other.x));
+        let b = self.y.add(&self.x).mul(&other.y.add(&other.x));
+        let c = self.t.mul(&D2).mul(&other.t);
+        let d = self.z.mul(&other.z).mul_small(2);
+        let (e, f, g, h) = (b.sub(&a), d.sub(&c), d.add(&c), b.add(&a));
+        Point {
+            x: e.mul(&f),
+            y: g.mul(&h),
+            z: f.mul(&g),
+            t: e.mul(&h),
+        }
+    }
+
+    /// negates the point
+    fn neg(&self) -> Self {
+        Point {
+            x: self.x.neg(),
+            y: self.y,
+            z: self.z,
+            t: self.t.neg(),
+        }
+    }
+
+    /// multiplies the point by a 256 bit little endian scalar in constant time
+    fn mul(&self, scalar: &[u8]) -> Self {
+        let (mut p, mut q) = (Point::IDENTITY, *self);
+        for i in (0..256).rev() {
+            let bit = and!(shift_right!(scalar[i / 8], (i % 8) as u32), 1) as u64;
+            Point::cswap(&mut p, &mut q, bit);
+            q = q.add(&p);
+            p = p.add(&p);
+            Point::cswap(&mut p, &mut q, bit);
+        }
+        p
+    }
+
+    /// swaps two points in constant time if `swap` is 1
+    fn cswap(a: &mut Self, b: &mut Self, swap: u64) {
+        Fe::cswap(&mut a.x, &mut b.x, swap);
+        Fe::cswap(&mut a.y, &mut b.y, swap);
+        Fe::cswap(&mut a.z, &mut b.z, swap);
+        Fe::cswap(&mut a.t, &mut b.t, swap);
+    }
+
+    /// returns whether the point has a small order, i.e. whether 8 times the point is the identity
+    fn is_small_order(&self) -> bool {
+        let p2 = self.add(self);
+        let p4 = p2.add(&p2);
+        let p8 = p4.add(&p4);
+        p8.x.is_zero()
+    }
+
+    /// encodes the point as the y coordinate with the sign of x in the top bit
+    fn encode(&self) -> [u8; 32] {
+        let z_inv = self.z.invert();
+        let (x, y) = (self.x.mul(&z_inv), self.y.mul(&z_inv));
+        let mut bytes = y.to_bytes();
+        bytes[31] = xor!(bytes[31], shift_left!(x.is_negative(), 7));
+        bytes
+    }
+
+    /// decodes a point and rejects non canonical encodings
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let y = Fe::from_bytes(bytes);
+        let sign = shift_right!(bytes[31], 7);
+
+        // reject y >= p
+        let mut canonical = y.to_bytes();
+        canonical[31] = or!(canonical[31], shift_left!(sign, 7));
+        if !eq_const_time!(&canonical, &bytes[..32]) {
+            return None;
+        }
+
+        // x^2 = (y^2 - 1) / (d y^2 + 1)
+        let y2 = y.square();
+        let u = y2.sub(&Fe::ONE);
+        let v = D.mul(&y2).add(&Fe::ONE);
+        let v3 = v.square().mul(&v);
+        let v7 = v3.square().mul(&v);
+        let mut x = u.mul(&v3).mul(&u.mul(&v7).pow_p58());
+
+        let vx2 = v.mul(&x.square());
+        if vx2.equals(&u.neg()) {
+            x = x.mul(&SQRT_M1);
+        } else if !vx2.equals(&u) {
+            return None;
+        }
+
+        if x.is_zero() && sign == 1 {
+            return None;
+        }
+        if x.is_negative() != sign {
+            x = x.neg();
+        }
+
+        Some(Point {
+            x,
+            y,
+            z: Fe::ONE,
+            t: x.mul(&y),
+        })
+    }
+}
+
+/// reduces the 64 signed limbs of `x` modulo L into `r`
+fn mod_l(r: &mut [u8; 32], x: &mut [i64; 64]) {
+    for i in (32..64).rev() {
+        let mut carry = 0;
+        let mut j = i - 32;
+        while j < i - 12 {
+            x[j] += carry - 16 * x[i] * L[j - (i - 32)];
+            carry = (x[j] + 128) >> 8;
+            x[j] -= carry << 8;
+            j += 1;
+        }
+        x[j] += carry;
+        x[i] = 0;
+    }
+
+    let mut carry = 0;
+    for j in 0..32 {
+        x[j] += carry - (x[31] >> 4) * L[j];
+        carry = x[j] >> 8;
+        x[j] &= 255;
+    }
+    (0..32).for_each(|j| x[j] -= carry * L[j]);
+    for i in 0..32 {
+        x[i + 1] += x[i] >> 8;
+        r[i] = (x[i] & 255) as u8;
+    }
+}
+
+/// reduces a 64 byte little endian number modulo L
+fn reduce(bytes: &[u8; 64]) -> [u8; 32] {
+    let mut x = [0i64; 64];
+    (0..64).for_each(|i| x[i] = bytes[i] as i64);
+    let mut r = [0u8; 32];
+    mod_l(&mut r, &mut x);
+    r
+}
+
+/// computes (a + b * c) mod L
+fn mul_add(a: &[u8; 32], b: &[u8; 32], c: &[u8; 32]) -> [u8; 32] {
+    let mut x = [0i64; 64];
+    (0..32).for_each(|i| x[i] = a[i] as i64);
+    for i in 0..32 {
+        for j in 0..32 {
+            x[i + j] += b[i] as i64 * c[j] as i64;
+        }
+    }
+    let mut r = [0u8; 32];
+    mod_l(&mut r, &mut x);
+    r
+}
+
+/// returns whether a scalar is fully reduced, i.e. less than L
+fn is_canonical(s: &[u8]) -> bool {
+    for i in (0..32).rev() {
+        match (s[i] as i64).cmp(&L[i]) {
+            std::cmp::Ordering::Less => return true,
+            std::cmp::Ordering::Greater => return false,
+            std::cmp::Ordering::Equal => (),
+        }
+    }
+    false
+}
+
+/// hashes the concatenated parts with SHA-512
+fn sha512(parts: &[&[u8]]) -> [u8; 64] {
+    let mut state = Sha512State::new();
+    parts.iter().for_each(|p| state.update(p));
+    let mut hash = [0u8; 64];
+    state.finish(&mut hash);
+    hash
+}
+
+/// expands the secret seed into the clamped secret scalar and the nonce prefix
+fn expand_seed(seed: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let hash = sha512(&[seed]);
+    let (mut scalar, mut prefix) = ([0u8; 32], [0u8; 32]);
+    scalar.copy_from_slice(&hash[..32]);
+    prefix.copy_from_slice(&hash[32..]);
+
+    scalar[0] = and!(scalar[0], 248);
+    scalar[31] = or!(and!(scalar[31], 127), 64);
+    (scalar, prefix)
+}
+
+/// derives the public key from a 32 byte secret seed
+pub fn ed25519_public_key(public_key: &mut [u8], seed: &[u8]) {
+    let (scalar, _) = expand_seed(seed);
+    public_key[..32].copy_from_slice(&BASE.mul(&scalar).encode());
+}
+
+/// signs the `data` with the 32 byte secret seed into the 64 byte `sig`
+pub fn ed25519_sign(sig: &mut [u8], data: &[u8], seed: &[u8]) {
+    let (scalar, prefix) = expand_seed(seed);
+    let public_key = BASE.mul(&scalar).encode();
+
+    // r = H(prefix || M), R = [r]B
+    let r = reduce(&sha512(&[&prefix, data]));
+    let big_r = BASE.mul(&r).encode();
+
+    // S = (r + H(R || A || M) * s) mod L
+    let k = reduce(&sha512(&[&big_r, &public_key, data]));
+    let s = mul_add(&r, &k, &scalar);
+
+    sig[..32].copy_from_slice(&big_r);
+    sig[32..64].copy_from_slice(&s);
+}
+
+/// verifies the 64 byte `sig` over the `data` with the 32 byte `public_key`. Non canonical encodings, small order
+/// points and unreduced scalars are rejected.
+pub fn ed25519_verify(sig: &[u8], data: &[u8], public_key: &[u8]) -> bool {
+    let (big_r, s) = (&sig[..32], &sig[32..64]);
+    if !is_canonical(s) {
+        return false;
+    }
+    let (a, r) = match (Point::decode(public_key), Point::decode(big_r)) {
+        (Some(a), Some(r)) => (a, r),
+        _ => return false,
+    };
+    if a.is_small_order() || r.is_small_order() {
+        return false;
+    }
+
+    // check [S]B - [k]A == R
+    let k = reduce(&sha512(&[big_r, &public_key[..32], data]));
+    let check = BASE.mul(s).add(&a.neg().mul(&k));
+    eq_const_time!(&check.encode(), &big_r)
+}
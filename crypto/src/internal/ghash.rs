@@ -0,0 +1,105 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+/// carryless multiplication of two 64 bit words which returns the lower 64 bits of the product. The bits are split
+/// into four interleaved groups with holes between them so that the integer multiplications can't carry into bits
+/// that are kept and the multiplication runs in constant time.
+fn bmul64(x: u64, y: u64) -> u64 {
+    const M: [u64; 4] = [
+        0x1111111111111111,
+        0x2222222222222222,
+        0x4444444444444444,
+        0x8888888888888888,
+    ];
+    let (x0, x1, x2, x3) = (and!(x, M[0]), and!(x, M[1]), and!(x, M[2]), and!(x, M[3]));
+    let (y0, y1, y2, y3) = (and!(y, M[0]), and!(y, M[1]), and!(y, M[2]), and!(y, M[3]));
+
+    let z0 = xor!(xor!(mult!(x0, y0), mult!(x1, y3)), xor!(mult!(x2, y2), mult!(x3, y1)));
+    let z1 = xor!(xor!(mult!(x0, y1), mult!(x1, y0)), xor!(mult!(x2, y3), mult!(x3, y2)));
+    let z2 = xor!(xor!(mult!(x0, y2), mult!(x1, y1)), xor!(mult!(x2, y0), mult!(x3, y3)));
+    let z3 = xor!(xor!(mult!(x0, y3), mult!(x1, y2)), xor!(mult!(x2, y1), mult!(x3, y0)));
+    or!(or!(and!(z0, M[0]), and!(z1, M[1])), or!(and!(z2, M[2]), and!(z3, M[3])))
+}
+
+/// The constant time software GHASH of GCM
+#[derive(Clone)]
+pub struct GhashSoft {
+    h: [u64; 2],
+    y: [u64; 2],
+}
+
+impl GhashSoft {
+    /// creates a new GHASH state with the 16 byte hash key `h`
+    pub fn new(h: &[u8]) -> Self {
+        Self {
+            h: [read64_big_endian!(&h[8..]), read64_big_endian!(&h[..8])],
+            y: [0; 2],
+        }
+    }
+
+    /// absorbs the data into the state. An incomplete last block is padded with zeroes.
+    pub fn update(&mut self, data: &[u8]) {
+        let [h0, h1] = self.h;
+        let (h0r, h1r) = (h0.reverse_bits(), h1.reverse_bits());
+        let (h2, h2r) = (xor!(h0, h1), xor!(h0r, h1r));
+
+        for chunk in data.chunks(16) {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            let y1 = xor!(self.y[1], read64_big_endian!(&block[..8]));
+            let y0 = xor!(self.y[0], read64_big_endian!(&block[8..]));
+
+            // karatsuba multiplication of the bit reflected values
+            let (y0r, y1r) = (y0.reverse_bits(), y1.reverse_bits());
+            let (y2, y2r) = (xor!(y0, y1), xor!(y0r, y1r));
+            let z0 = bmul64(y0, h0);
+            let z1 = bmul64(y1, h1);
+            let mut z2 = bmul64(y2, h2);
+            let mut z0h = bmul64(y0r, h0r);
+            let mut z1h = bmul64(y1r, h1r);
+            let mut z2h = bmul64(y2r, h2r);
+            z2 = xor!(z2, xor!(z0, z1));
+            z2h = xor!(z2h, xor!(z0h, z1h));
+            z0h = shift_right!(z0h.reverse_bits(), 1);
+            z1h = shift_right!(z1h.reverse_bits(), 1);
+            z2h = shift_right!(z2h.reverse_bits(), 1);
+
+            let mut v = [z0, xor!(z0h, z2), xor!(z1, z2h), z1h];
+
+            // shift the 256 bit product left by one bit
+            v[3] = or!(shift_left!(v[3], 1), shift_right!(v[2], 63));
+            v[2] = or!(shift_left!(v[2], 1), shift_right!(v[1], 63));
+            v[1] = or!(shift_left!(v[1], 1), shift_right!(v[0], 63));
+            v[0] = shift_left!(v[0], 1);
+
+            // reduce modulo x^128 + x^7 + x^2 + x + 1
+            let fold_right = |v: u64| {
+                xor!(
+                    xor!(v, shift_right!(v, 1)),
+                    xor!(shift_right!(v, 2), shift_right!(v, 7))
+                )
+            };
+            let fold_left = |v: u64| xor!(xor!(shift_left!(v, 63), shift_left!(v, 62)), shift_left!(v, 57));
+            v[2] = xor!(v[2], fold_right(v[0]));
+            v[1] = xor!(v[1], fold_left(v[0]));
+            v[3] = xor!(v[3], fold_right(v[1]));
+            v[2] = xor!(v[2], fold_left(v[1]));
+
+            self.y = [v[2], v[3]];
+        }
+    }
+
+    /// writes the 16 byte hash into `buf`
+    pub fn finish(self, buf: &mut [u8]) {
+        write64_big_endian!(self.y[1] => &mut buf[..8]);
+        write64_big_endian!(self.y[0] => &mut buf[8..]);
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::hmac::{HmacSha256State, HmacSha512State};
+
+/// builds the HKDF extract and expand steps on top of an HMAC state with the given digest size.
+macro_rules! hkdf {
+    ($extract:ident, $expand:ident, $hmac:ident, $len:expr) => {
+        /// extracts a pseudorandom key of `$len` bytes from the input key material into `prk`
+        pub fn $extract(prk: &mut [u8], salt: &[u8], ikm: &[u8]) {
+            // an absent salt is a string of zeroes of the digest size
+            let zeroes = [0u8; $len];
+            let salt = match salt.is_empty() {
+                true => &zeroes[..],
+                false => salt,
+            };
+
+            let mut state = $hmac::new(salt);
+            state.update(ikm);
+            state.finish(&mut prk[..$len]);
+        }
+
+        /// expands the pseudorandom key `prk` with `info` into `buf`
+        pub fn $expand(buf: &mut [u8], prk: &[u8], info: &[u8]) {
+            let mut block = [0u8; $len];
+            for (i, chunk) in buf.chunks_mut($len).enumerate() {
+                // T(i) = HMAC(PRK, T(i - 1) || info || i)
+                let mut state = $hmac::new(prk);
+                if i > 0 {
+                    state.update(&block);
+                }
+                state.update(info);
+                state.update(&[add!(i as u8, 1)]);
+                state.finish(&mut block);
+
+                chunk.copy_from_slice(&block[..chunk.len()]);
+            }
+        }
+    };
+}
+
+hkdf!(hkdf_sha256_extract, hkdf_sha256_expand, HmacSha256State, 32);
+hkdf!(hkdf_sha512_extract, hkdf_sha512_expand, HmacSha512State, 64);
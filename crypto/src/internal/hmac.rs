@@ -0,0 +1,67 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::sha2::{Sha256State, Sha512State};
+
+/// builds an HMAC state on top of a hash state with the given block and digest sizes.
+macro_rules! hmac_state {
+    ($name:ident, $hash:ident, $block:expr, $len:expr) => {
+        /// The running state of an HMAC computation
+        #[derive(Clone)]
+        pub struct $name {
+            inner: $hash,
+            outer: $hash,
+        }
+
+        impl $name {
+            /// creates a new HMAC state with a key of any length
+            pub fn new(key: &[u8]) -> Self {
+                // keys longer than the block size are hashed first
+                let mut k = [0u8; $block];
+                if key.len() > $block {
+                    let mut hash = $hash::new();
+                    hash.update(key);
+                    hash.finish(&mut k[..$len]);
+                } else {
+                    k[..key.len()].copy_from_slice(key);
+                }
+
+                // absorb the inner and outer padded keys
+                let (mut inner, mut outer) = ($hash::new(), $hash::new());
+                let mut pad = [0u8; $block];
+                (0..$block).for_each(|i| pad[i] = xor!(k[i], 0x36));
+                inner.update(&pad);
+                (0..$block).for_each(|i| pad[i] = xor!(k[i], 0x5c));
+                outer.update(&pad);
+
+                Self { inner, outer }
+            }
+
+            /// absorbs data into the state
+            pub fn update(&mut self, data: &[u8]) {
+                self.inner.update(data);
+            }
+
+            /// writes the full length tag into `buf`
+            pub fn finish(self, buf: &mut [u8]) {
+                let mut inner_hash = [0u8; $len];
+                self.inner.finish(&mut inner_hash);
+
+                let mut outer = self.outer;
+                outer.update(&inner_hash);
+                outer.finish(buf);
+            }
+        }
+    };
+}
+
+hmac_state!(HmacSha256State, Sha256State, 64, 32);
+hmac_state!(HmacSha512State, Sha512State, 128, 64);
@@ -0,0 +1,46 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::hmac::{HmacSha256State, HmacSha512State};
+
+/// builds PBKDF2 on top of an HMAC state with the given digest size.
+macro_rules! pbkdf2 {
+    ($name:ident, $hmac:ident, $len:expr) => {
+        /// derives `buf.len()` bytes from the `password` and `salt` with `iterations` rounds
+        pub fn $name(buf: &mut [u8], password: &[u8], salt: &[u8], iterations: u64) {
+            // the keyed state is computed once and cloned for each HMAC invocation
+            let keyed = $hmac::new(password);
+
+            for (i, chunk) in buf.chunks_mut($len).enumerate() {
+                // U_1 = PRF(P, S || INT(i))
+                let mut u = [0u8; $len];
+                let mut state = keyed.clone();
+                state.update(salt);
+                state.update(&add!(i as u32, 1).to_be_bytes());
+                state.finish(&mut u);
+
+                // T_i = U_1 ^ U_2 ^ ... ^ U_c
+                let mut t = u;
+                for _ in 1..iterations {
+                    let mut state = keyed.clone();
+                    state.update(&u);
+                    state.finish(&mut u);
+                    (0..$len).for_each(|j| t[j] = xor!(t[j], u[j]));
+                }
+
+                chunk.copy_from_slice(&t[..chunk.len()]);
+            }
+        }
+    };
+}
+
+pbkdf2!(pbkdf2_sha256, HmacSha256State, 32);
+pbkdf2!(pbkdf2_sha512, HmacSha512State, 64);
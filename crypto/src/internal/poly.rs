@@ -11,6 +11,26 @@
 
 use std::cmp::min;
 
+/// the Poly1305 backend for the target's word size
+#[cfg(target_pointer_width = "64")]
+pub type Poly1305Limbs = crate::internal::poly64::Poly1305Limbs64;
+/// the Poly1305 backend for the target's word size
+#[cfg(not(target_pointer_width = "64"))]
+pub type Poly1305Limbs = Poly1305Limbs32;
+
+/// A Poly1305 implementation that absorbs blocks into its accumulator
+pub trait Poly1305Backend {
+    /// loads a key into r and s and computes the key multipliers
+    fn new(key: &[u8]) -> Self;
+
+    /// updates the value a with any data, pads an incomplete last block with 0 bytes
+    fn update(&mut self, data: &[u8], is_last: bool);
+
+    /// finishes authentication
+    fn finish(self, tag: &mut [u8]);
+}
+
+#[cfg(any(test, not(target_pointer_width = "64")))]
 /// Loads a key into r and s and computes the key multipliers
 pub fn poly1305_init(r: &mut [u32], s: &mut [u32], mu: &mut [u32], key: &[u8]) {
     // load key
@@ -33,11 +53,12 @@ pub fn poly1305_init(r: &mut [u32], s: &mut [u32], mu: &mut [u32], key: &[u8]) {
     mu[4] = mult!(r[4], 5);
 }
 
+#[cfg(any(test, not(target_pointer_width = "64")))]
 /// updates the value a with any data using the key and the multipliers
 /// pads any incomplete block with 0 bytes.
 pub fn poly1305_update(a: &mut [u32], r: &[u32], mu: &[u32], mut data: &[u8], is_last: bool) {
-    let mut buf = vec![0; 16];
-    let mut w = vec![0; 5];
+    let mut buf = [0; 16];
+    let mut w = [0; 5];
 
     // process data
     while !data.is_empty() {
@@ -150,6 +171,7 @@ pub fn poly1305_update(a: &mut [u32], r: &[u32], mu: &[u32], mut data: &[u8], is
     }
 }
 
+#[cfg(any(test, not(target_pointer_width = "64")))]
 /// finishes authentication
 pub fn poly1305_finish(tag: &mut [u8], a: &mut [u32], s: &[u32]) {
     // modular reduction
@@ -198,3 +220,156 @@ pub fn poly1305_finish(tag: &mut [u8], a: &mut [u32], s: &[u32]) {
     word = add!(shift_right!(word, 32) as u32, shift_left!(a[4], 8), s[3]) as u64;
     write32_little_endian!(word as u32 => &mut tag[12..]);
 }
+
+/// Poly1305 with 26 bit limbs and 32 bit multiplications for 32 bit targets
+#[cfg(any(test, not(target_pointer_width = "64")))]
+#[derive(Clone)]
+pub struct Poly1305Limbs32 {
+    r: [u32; 5],
+    s: [u32; 4],
+    mu: [u32; 5],
+    a: [u32; 5],
+}
+
+#[cfg(any(test, not(target_pointer_width = "64")))]
+impl Poly1305Backend for Poly1305Limbs32 {
+    fn new(key: &[u8]) -> Self {
+        let mut this = Self {
+            r: [0; 5],
+            s: [0; 4],
+            mu: [0; 5],
+            a: [0; 5],
+        };
+        poly1305_init(&mut this.r, &mut this.s, &mut this.mu, key);
+        this
+    }
+
+    fn update(&mut self, data: &[u8], is_last: bool) {
+        poly1305_update(&mut self.a, &self.r, &self.mu, data, is_last);
+    }
+
+    fn finish(mut self, tag: &mut [u8]) {
+        poly1305_finish(tag, &mut self.a, &self.s);
+    }
+}
+
+/// The running state of a Poly1305 computation on the target's backend
+pub struct Poly1305State<L = Poly1305Limbs> {
+    limbs: L,
+    buf: [u8; 16],
+    buf_len: usize,
+}
+
+impl Poly1305State {
+    /// creates a new Poly1305 state with a 32 byte one-time key
+    pub fn new(key: &[u8]) -> Self {
+        Self::with_backend(key)
+    }
+}
+
+impl<L: Poly1305Backend> Poly1305State<L> {
+    /// creates a new Poly1305 state on the backend `L` with a 32 byte one-time key
+    pub fn with_backend(key: &[u8]) -> Self {
+        Self {
+            limbs: L::new(key),
+            buf: [0; 16],
+            buf_len: 0,
+        }
+    }
+
+    /// absorbs data into the state
+    pub fn update(&mut self, mut data: &[u8]) {
+        // only full blocks are processed so that the final partial block is padded correctly
+        if self.buf_len > 0 {
+            let to_copy = min(data.len(), 16 - self.buf_len);
+            self.buf[self.buf_len..self.buf_len + to_copy].copy_from_slice(&data[..to_copy]);
+            self.buf_len += to_copy;
+            data = &data[to_copy..];
+
+            if self.buf_len < 16 {
+                return;
+            }
+            self.limbs.update(&self.buf, false);
+            self.buf_len = 0;
+        }
+
+        let full = data.len() - data.len() % 16;
+        self.limbs.update(&data[..full], false);
+
+        let rest = &data[full..];
+        self.buf[..rest.len()].copy_from_slice(rest);
+        self.buf_len = rest.len();
+    }
+
+    /// absorbs data with an incomplete last block padded with 0 bytes, as done for the AEAD construction. Must only be
+    /// called at a block boundary.
+    pub fn update_padded(&mut self, data: &[u8]) {
+        debug_assert_eq!(self.buf_len, 0);
+        self.limbs.update(data, false);
+    }
+
+    /// processes the remaining data and writes the 16 byte tag into `tag`
+    pub fn finish(mut self, tag: &mut [u8]) {
+        self.limbs.update(&self.buf[..self.buf_len], true);
+        self.limbs.finish(tag);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Poly1305Backend, Poly1305Limbs32, Poly1305State};
+    use crate::internal::poly64::Poly1305Limbs64;
+
+    // xorshift64* to draw keys, lengths and splits without an RNG dependency
+    struct TestRng(u64);
+    impl TestRng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545f4914f6cdd1d)
+        }
+
+        fn fill(&mut self, buf: &mut [u8]) {
+            buf.iter_mut().for_each(|b| *b = self.next() as u8);
+        }
+    }
+
+    // feeds the data in chunks of odd sizes
+    fn tag<L: Poly1305Backend>(key: &[u8], mut data: &[u8], splits: &[usize]) -> [u8; 16] {
+        let mut state = Poly1305State::<L>::with_backend(key);
+        for split in splits {
+            let (chunk, rest) = data.split_at(*split);
+            state.update(chunk);
+            data = rest;
+        }
+        state.update(data);
+
+        let mut tag = [0; 16];
+        state.finish(&mut tag);
+        tag
+    }
+
+    // the 26 and 44 bit backends must produce the same tags for any key and any split of the input
+    #[test]
+    fn test_backends() {
+        let mut rng = TestRng(0x9e3779b97f4a7c15);
+        let (mut key, mut data) = ([0; 32], [0; 1100]);
+
+        for len in 0..=1100 {
+            rng.fill(&mut key);
+            rng.fill(&mut data[..len]);
+
+            let (mut splits, mut left) = (Vec::new(), len);
+            while left > 0 {
+                let split = (2 * (rng.next() % 300) + 1) as usize;
+                splits.push(split.min(left));
+                left -= split.min(left);
+            }
+
+            let tag32 = tag::<Poly1305Limbs32>(&key, &data[..len], &splits);
+            let tag64 = tag::<Poly1305Limbs64>(&key, &data[..len], &splits);
+            assert_eq!(tag32, tag64, "Length: {}", len);
+        }
+    }
+}
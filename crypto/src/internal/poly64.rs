@@ -0,0 +1,160 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::poly::Poly1305Backend;
+
+/// mask of a 44 bit limb
+const MASK44: u64 = 0xfffffffffff;
+/// mask of the highest 42 bit limb
+const MASK42: u64 = 0x3ffffffffff;
+
+/// multiplies two limbs into a 128 bit product
+macro_rules! m {
+    ($a:expr, $b:expr) => {{
+        mult!($a as u128, $b as u128)
+    }};
+}
+
+/// Poly1305 with 44 bit limbs and 64 bit multiplications for 64 bit targets
+#[derive(Clone)]
+pub struct Poly1305Limbs64 {
+    r: [u64; 3],
+    s: [u64; 2],
+    mu: [u64; 2],
+    a: [u64; 3],
+}
+
+impl Poly1305Backend for Poly1305Limbs64 {
+    /// loads a key into r and s and computes the key multipliers
+    fn new(key: &[u8]) -> Self {
+        // load key
+        let t0 = read64_little_endian!(&key[0..]);
+        let t1 = read64_little_endian!(&key[8..]);
+        let r = [
+            and!(t0, 0xffc0fffffff),
+            and!(or!(shift_right!(t0, 44), shift_left!(t1, 20)), 0xfffffc0ffff),
+            and!(shift_right!(t1, 24), 0x00ffffffc0f),
+        ];
+        let s = [read64_little_endian!(&key[16..]), read64_little_endian!(&key[24..])];
+
+        // compute multipliers. Products above 2^130 wrap around multiplied by 5 and shifted by the 2 bits that the top
+        // limb is shorter.
+        let mu = [mult!(r[1], 20), mult!(r[2], 20)];
+
+        Self { r, s, mu, a: [0; 3] }
+    }
+
+    /// updates the value a with any data. Pads any incomplete block with 0 bytes, and appends a 0x01 byte to an
+    /// incomplete last block instead of the high bit.
+    fn update(&mut self, data: &[u8], is_last: bool) {
+        let [r0, r1, r2] = self.r;
+        let [mu1, mu2] = self.mu;
+
+        for chunk in data.chunks(16) {
+            // put data into buffer and append 0x01 byte as padding as needed
+            let mut buf = [0u8; 16];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            let high_bit = if chunk.len() < 16 && is_last {
+                buf[chunk.len()] = 0x01;
+                0
+            } else {
+                shift_left!(1u64, 40)
+            };
+
+            // decode next block into the accumulator
+            let t0 = read64_little_endian!(&buf[0..]);
+            let t1 = read64_little_endian!(&buf[8..]);
+            let a0 = add!(self.a[0], and!(t0, MASK44));
+            let a1 = add!(self.a[1], and!(or!(shift_right!(t0, 44), shift_left!(t1, 20)), MASK44));
+            let a2 = add!(self.a[2], or!(and!(shift_right!(t1, 24), MASK42), high_bit));
+
+            // multiply
+            let w0 = add!(m!(a0, r0), m!(a1, mu2), m!(a2, mu1));
+            let mut w1 = add!(m!(a0, r1), m!(a1, r0), m!(a2, mu2));
+            let mut w2 = add!(m!(a0, r2), m!(a1, r1), m!(a2, r0));
+
+            // modular reduction
+            let mut c;
+            c = shift_right!(w0, 44) as u64;
+            let mut a0 = and!(w0 as u64, MASK44);
+            w1 = add!(w1, c as u128);
+            c = shift_right!(w1, 44) as u64;
+            let a1 = and!(w1 as u64, MASK44);
+            w2 = add!(w2, c as u128);
+            c = shift_right!(w2, 42) as u64;
+            let a2 = and!(w2 as u64, MASK42);
+
+            a0 = add!(a0, mult!(c, 5));
+            c = shift_right!(a0, 44);
+            a0 = and!(a0, MASK44);
+            self.a = [a0, add!(a1, c), a2];
+        }
+    }
+
+    /// finishes authentication
+    fn finish(self, tag: &mut [u8]) {
+        let [mut a0, mut a1, mut a2] = self.a;
+
+        // fully carry the accumulator
+        let mut c;
+        c = shift_right!(a1, 44);
+        a1 = and!(a1, MASK44);
+        a2 = add!(a2, c);
+        c = shift_right!(a2, 42);
+        a2 = and!(a2, MASK42);
+        a0 = add!(a0, mult!(c, 5));
+        c = shift_right!(a0, 44);
+        a0 = and!(a0, MASK44);
+        a1 = add!(a1, c);
+        c = shift_right!(a1, 44);
+        a1 = and!(a1, MASK44);
+        a2 = add!(a2, c);
+        c = shift_right!(a2, 42);
+        a2 = and!(a2, MASK42);
+        a0 = add!(a0, mult!(c, 5));
+        c = shift_right!(a0, 44);
+        a0 = and!(a0, MASK44);
+        a1 = add!(a1, c);
+
+        // compute a + -(2^130 - 5) and select it if the value is in the range [2^130-5, 2^130)
+        let mut g0 = add!(a0, 5);
+        c = shift_right!(g0, 44);
+        g0 = and!(g0, MASK44);
+        let mut g1 = add!(a1, c);
+        c = shift_right!(g1, 44);
+        g1 = and!(g1, MASK44);
+        let mut g2 = sub!(add!(a2, c), shift_left!(1u64, 42));
+
+        let mux = sub!(shift_right!(g2, 63), 1);
+        g0 = and!(g0, mux);
+        g1 = and!(g1, mux);
+        g2 = and!(g2, mux);
+        a0 = or!(and!(a0, !mux), g0);
+        a1 = or!(and!(a1, !mux), g1);
+        a2 = or!(and!(a2, !mux), g2);
+
+        // add second half of key mod 2^128
+        let [s0, s1] = self.s;
+        a0 = add!(a0, and!(s0, MASK44));
+        c = shift_right!(a0, 44);
+        a0 = and!(a0, MASK44);
+        a1 = add!(a1, and!(or!(shift_right!(s0, 44), shift_left!(s1, 20)), MASK44), c);
+        c = shift_right!(a1, 44);
+        a1 = and!(a1, MASK44);
+        a2 = and!(add!(a2, and!(shift_right!(s1, 24), MASK42), c), MASK42);
+
+        // convert back to 64 bit words
+        let word0 = or!(a0, shift_left!(a1, 44));
+        let word1 = or!(shift_right!(a1, 20), shift_left!(a2, 24));
+        write64_little_endian!(word0 => &mut tag[0..]);
+        write64_little_endian!(word1 => &mut tag[8..]);
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::ghash::GhashSoft;
+
+/// The constant time software POLYVAL of AES-GCM-SIV. It is computed with GHASH using the relation from RFC 8452,
+/// Appendix A: the hash key is byte reversed and multiplied by x and every block is byte reversed.
+#[derive(Clone)]
+pub struct PolyvalSoft {
+    ghash: GhashSoft,
+}
+
+impl PolyvalSoft {
+    /// creates a new POLYVAL state with the 16 byte hash key `h`
+    pub fn new(h: &[u8]) -> Self {
+        let mut key = [0u8; 16];
+        key.iter_mut().zip(h.iter().rev()).for_each(|(k, h)| *k = *h);
+
+        // multiply by x in the bit reflected representation of GHASH
+        let carry = and!(key[15], 1);
+        for i in (1..16).rev() {
+            key[i] = or!(shift_right!(key[i], 1), shift_left!(key[i - 1], 7));
+        }
+        key[0] = xor!(shift_right!(key[0], 1), and!(negate!(carry), 0xe1));
+
+        Self {
+            ghash: GhashSoft::new(&key),
+        }
+    }
+
+    /// absorbs the data into the state. An incomplete last block is padded with zeroes.
+    pub fn update(&mut self, data: &[u8]) {
+        for chunk in data.chunks(16) {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            block.reverse();
+            self.ghash.update(&block);
+        }
+    }
+
+    /// writes the 16 byte hash into `buf`
+    pub fn finish(self, buf: &mut [u8]) {
+        self.ghash.finish(&mut buf[..16]);
+        buf[..16].reverse();
+    }
+}
@@ -0,0 +1,528 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::{hmac::HmacSha256State, sha2::Sha256State};
+
+/// A prime modulus for Montgomery arithmetic on four 64 bit limbs
+struct Modulus {
+    /// the modulus in little endian limbs
+    m: [u64; 4],
+    /// R^2 mod m with R = 2^256
+    r2: [u64; 4],
+    /// -m^-1 mod 2^64
+    inv: u64,
+}
+
+/// the field prime p = 2^256 - 2^32 - 977
+const P: Modulus = Modulus {
+    m: [
+        0xfffffffefffffc2f,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ],
+    r2: [0x000007a2000e90a1, 0x1, 0x0, 0x0],
+    inv: 0xd838091dd2253531,
+};
+/// the group order n
+const N: Modulus = Modulus {
+    m: [
+        0xbfd25e8cd0364141,
+        0xbaaedce6af48a03b,
+        0xfffffffffffffffe,
+        0xffffffffffffffff,
+    ],
+    r2: [
+        0x896cf21467d7d140,
+        0x741496c20e7cf878,
+        0xe697f5e45bcd07c6,
+        0x9d671cd581c69bc5,
+    ],
+    inv: 0x4b0dff665588b13f,
+};
+/// half the group order, the largest accepted s value
+const N_HALF: [u64; 4] = [
+    0xdfe92f46681b20a0,
+    0x5d576e7357a4501d,
+    0xffffffffffffffff,
+    0x7fffffffffffffff,
+];
+/// the x coordinate of the generator
+const GX: [u8; 32] = [
+    0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce, 0x87, 0x0b, 0x07, 0x02, 0x9b, 0xfc,
+    0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81, 0x5b, 0x16, 0xf8, 0x17, 0x98,
+];
+/// the y coordinate of the generator
+const GY: [u8; 32] = [
+    0x48, 0x3a, 0xda, 0x77, 0x26, 0xa3, 0xc4, 0x65, 0x5d, 0xa4, 0xfb, 0xfc, 0x0e, 0x11, 0x08, 0xa8, 0xfd, 0x17, 0xb4,
+    0x48, 0xa6, 0x85, 0x54, 0x19, 0x9c, 0x47, 0xd0, 0x8f, 0xfb, 0x10, 0xd4, 0xb8,
+];
+
+/// computes a + b * c + carry and returns the low and high words
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = add!(a as u128, mult!(b as u128, c as u128), carry as u128);
+    (t as u64, shift_right!(t, 64) as u64)
+}
+
+/// computes a + b + carry and returns the sum and the carry
+fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = add!(a as u128, b as u128, carry as u128);
+    (t as u64, shift_right!(t, 64) as u64)
+}
+
+/// computes a - b - borrow and returns the difference and the borrow (0 or 1)
+fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = sub!(sub!(a as u128, b as u128), borrow as u128);
+    (t as u64, shift_right!(t, 127) as u64)
+}
+
+/// selects `b` if `flag` is 1 and `a` if it is 0 in constant time
+fn select(a: &[u64; 4], b: &[u64; 4], flag: u64) -> [u64; 4] {
+    let mask = negate!(flag);
+    let mut r = [0u64; 4];
+    (0..4).for_each(|i| r[i] = xor!(a[i], and!(mask, xor!(a[i], b[i]))));
+    r
+}
+
+/// subtracts two 256 bit numbers and returns the difference and the borrow
+fn sub256(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let (mut r, mut borrow) = ([0u64; 4], 0);
+    (0..4).for_each(|i| {
+        let (d, b) = sbb(a[i], b[i], borrow);
+        r[i] = d;
+        borrow = b;
+    });
+    (r, borrow)
+}
+
+/// returns 1 if a < b and 0 otherwise
+fn less_than(a: &[u64; 4], b: &[u64; 4]) -> u64 {
+    sub256(a, b).1
+}
+
+/// returns whether the number is zero
+fn is_zero(a: &[u64; 4]) -> bool {
+    or!(or!(a[0], a[1]), or!(a[2], a[3])) == 0
+}
+
+/// decodes a 32 byte big endian number
+pub fn from_bytes(bytes: &[u8]) -> [u64; 4] {
+    let mut r = [0u64; 4];
+    (0..4).for_each(|i| r[3 - i] = read64_big_endian!(&bytes[i * 8..]));
+    r
+}
+
+/// encodes a number as 32 big endian bytes
+fn to_bytes(a: &[u64; 4]) -> [u8; 32] {
+    let mut r = [0u8; 32];
+    (0..4).for_each(|i| write64_big_endian!(a[3 - i] => &mut r[i * 8..]));
+    r
+}
+
+impl Modulus {
+    /// modular addition
+    fn add(&self, a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        let (mut s, mut carry) = ([0u64; 4], 0);
+        (0..4).for_each(|i| {
+            let (v, c) = adc(a[i], b[i], carry);
+            s[i] = v;
+            carry = c;
+        });
+        let (d, borrow) = sub256(&s, &self.m);
+        // keep the sum if it did not overflow and is less than m
+        select(&d, &s, and!(not_bool!(carry), borrow))
+    }
+
+    /// modular subtraction
+    fn sub(&self, a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        let (d, borrow) = sub256(a, b);
+        let (mut s, mut carry) = ([0u64; 4], 0);
+        (0..4).for_each(|i| {
+            let (v, c) = adc(d[i], self.m[i], carry);
+            s[i] = v;
+            carry = c;
+        });
+        select(&d, &s, borrow)
+    }
+
+    /// Montgomery multiplication a * b / R mod m
+    fn mul(&self, a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        let mut t = [0u64; 6];
+        for b_i in b.iter() {
+            let mut carry = 0;
+            for j in 0..4 {
+                let (v, c) = mac(t[j], a[j], *b_i, carry);
+                t[j] = v;
+                carry = c;
+            }
+            let (v, c) = adc(t[4], carry, 0);
+            t[4] = v;
+            t[5] = c;
+
+            let k = mult!(t[0], self.inv);
+            let (_, mut carry) = mac(t[0], k, self.m[0], 0);
+            for j in 1..4 {
+                let (v, c) = mac(t[j], k, self.m[j], carry);
+                t[j - 1] = v;
+                carry = c;
+            }
+            let (v, c) = adc(t[4], carry, 0);
+            t[3] = v;
+            t[4] = add!(t[5], c);
+        }
+
+        let r = [t[0], t[1], t[2], t[3]];
+        let (d, borrow) = sub256(&r, &self.m);
+        select(&d, &r, and!(not_bool!(t[4]), borrow))
+    }
+
+    /// converts into the Montgomery domain
+    fn to_mont(&self, a: &[u64; 4]) -> [u64; 4] {
+        self.mul(a, &self.r2)
+    }
+
+    /// converts out of the Montgomery domain
+    fn to_normal(&self, a: &[u64; 4]) -> [u64; 4] {
+        self.mul(a, &[1, 0, 0, 0])
+    }
+
+    /// the Montgomery representation of one
+    fn one(&self) -> [u64; 4] {
+        self.to_mont(&[1, 0, 0, 0])
+    }
+
+    /// raises a Montgomery number to a public exponent
+    fn pow(&self, a: &[u64; 4], e: &[u64; 4]) -> [u64; 4] {
+        let mut r = self.one();
+        for i in (0..256).rev() {
+            r = self.mul(&r, &r);
+            if and!(shift_right!(e[i / 64], (i % 64) as u32), 1) == 1 {
+                r = self.mul(&r, a);
+            }
+        }
+        r
+    }
+
+    /// computes the inverse of a Montgomery number as a^(m - 2)
+    fn invert(&self, a: &[u64; 4]) -> [u64; 4] {
+        let (e, _) = sub256(&self.m, &[2, 0, 0, 0]);
+        self.pow(a, &e)
+    }
+
+    /// reduces a 256 bit number modulo m, which is less than 2 * m for both moduli
+    fn reduce(&self, a: &[u64; 4]) -> [u64; 4] {
+        let (d, borrow) = sub256(a, &self.m);
+        select(&d, a, borrow)
+    }
+}
+
+/// A point in homogeneous projective coordinates with the field elements in Montgomery form
+#[derive(Clone, Copy)]
+struct Point {
+    x: [u64; 4],
+    y: [u64; 4],
+    z: [u64; 4],
+}
+
+impl Point {
+    /// the point at infinity
+    fn identity() -> Self {
+        Point {
+            x: [0; 4],
+            y: P.one(),
+            z: [0; 4],
+        }
+    }
+
+    /// the generator
+    fn generator() -> Self {
+        Point {
+            x: P.to_mont(&from_bytes(&GX)),
+            y: P.to_mont(&from_bytes(&GY)),
+            z: P.one(),
+        }
+    }
+
+    /// adds two points with the complete formula for a = 0 by Renes, Costello and Batina
+    fn add(&self, other: &Self) -> Self {
+        let b3 = P.to_mont(&[21, 0, 0, 0]);
+        let (x1, y1, z1) = (&self.x, &self.y, &self.z);
+        let (x2, y2, z2) = (&other.x, &other.y, &other.z);
+
+        let mut t0 = P.mul(x1, x2);
+        let mut t1 = P.mul(y1, y2);
+        let mut t2 = P.mul(z1, z2);
+        let mut t3 = P.mul(&P.add(x1, y1), &P.add(x2, y2));
+        let mut t4 = P.add(&t0, &t1);
+        t3 = P.sub(&t3, &t4);
+        t4 = P.mul(&P.add(y1, z1), &P.add(y2, z2));
+        let mut x3 = P.add(&t1, &t2);
+        t4 = P.sub(&t4, &x3);
+        x3 = P.mul(&P.add(x1, z1), &P.add(x2, z2));
+        let mut y3 = P.add(&t0, &t2);
+        y3 = P.sub(&x3, &y3);
+        x3 = P.add(&t0, &t0);
+        t0 = P.add(&x3, &t0);
+        t2 = P.mul(&b3, &t2);
+        let mut z3 = P.add(&t1, &t2);
+        t1 = P.sub(&t1, &t2);
+        y3 = P.mul(&b3, &y3);
+        x3 = P.mul(&t4, &y3);
+        t2 = P.mul(&t3, &t1);
+        x3 = P.sub(&t2, &x3);
+        y3 = P.mul(&y3, &t0);
+        t1 = P.mul(&t1, &z3);
+        y3 = P.add(&t1, &y3);
+        t0 = P.mul(&t0, &t3);
+        z3 = P.mul(&z3, &t4);
+        z3 = P.add(&z3, &t0);
+
+        Point { x: x3, y: y3, z: z3 }
+    }
+
+    /// multiplies the point by a 256 bit scalar in constant time
+    fn mul(&self, scalar: &[u64; 4]) -> Self {
+        let (mut r0, mut r1) = (Point::identity(), *self);
+        for i in (0..256).rev() {
+            let bit = and!(shift_right!(scalar[i / 64], (i % 64) as u32), 1);
+            Point::cswap(&mut r0, &mut r1, bit);
+            r1 = r1.add(&r0);
+            r0 = r0.add(&r0);
+            Point::cswap(&mut r0, &mut r1, bit);
+        }
+        r0
+    }
+
+    /// swaps two points in constant time if `swap` is 1
+    fn cswap(a: &mut Self, b: &mut Self, swap: u64) {
+        let (ax, ay, az) = (a.x, a.y, a.z);
+        a.x = select(&a.x, &b.x, swap);
+        a.y = select(&a.y, &b.y, swap);
+        a.z = select(&a.z, &b.z, swap);
+        b.x = select(&b.x, &ax, swap);
+        b.y = select(&b.y, &ay, swap);
+        b.z = select(&b.z, &az, swap);
+    }
+
+    /// returns the affine coordinates in normal form or `None` for the point at infinity
+    fn to_affine(self) -> Option<([u64; 4], [u64; 4])> {
+        if is_zero(&self.z) {
+            return None;
+        }
+        let z_inv = P.invert(&self.z);
+        Some((
+            P.to_normal(&P.mul(&self.x, &z_inv)),
+            P.to_normal(&P.mul(&self.y, &z_inv)),
+        ))
+    }
+
+    /// creates a point from an x coordinate and the parity of y, if x is on the curve
+    fn from_x(x: &[u64; 4], odd: u64) -> Option<Self> {
+        if less_than(x, &P.m) == 0 {
+            return None;
+        }
+
+        // y^2 = x^3 + 7, with p = 3 mod 4 the square root is (y^2)^((p + 1) / 4)
+        let xm = P.to_mont(x);
+        let y2 = P.add(&P.mul(&P.mul(&xm, &xm), &xm), &P.to_mont(&[7, 0, 0, 0]));
+        let e = [
+            0xffffffffbfffff0c,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0x3fffffffffffffff,
+        ];
+        let mut y = P.pow(&y2, &e);
+        if P.mul(&y, &y) != y2 {
+            return None;
+        }
+        if and!(P.to_normal(&y)[0], 1) != odd {
+            y = P.sub(&[0; 4], &y);
+        }
+
+        Some(Point { x: xm, y, z: P.one() })
+    }
+
+    /// decodes a compressed (33 bytes) or uncompressed (65 bytes) SEC1 public key
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        match (bytes.len(), bytes[0]) {
+            (33, 0x02) | (33, 0x03) => Point::from_x(&from_bytes(&bytes[1..]), (bytes[0] & 1) as u64),
+            (65, 0x04) => {
+                let (x, y) = (from_bytes(&bytes[1..33]), from_bytes(&bytes[33..]));
+                let point = Point::from_x(&x, and!(y[0], 1))?;
+                match P.to_normal(&point.y) == y {
+                    true => Some(point),
+                    false => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// encodes the point as a compressed SEC1 public key
+    fn encode(self) -> Option<[u8; 33]> {
+        let (x, y) = self.to_affine()?;
+        let mut out = [0u8; 33];
+        out[0] = or!(0x02, and!(y[0], 1) as u8);
+        out[1..].copy_from_slice(&to_bytes(&x));
+        Some(out)
+    }
+}
+
+/// hashes the data with SHA-256
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut state = Sha256State::new();
+    state.update(data);
+    let mut hash = [0u8; 32];
+    state.finish(&mut hash);
+    hash
+}
+
+/// checks whether the secret key is a valid scalar (0 < d < n)
+pub fn secp256k1_valid_secret_key(secret_key: &[u8]) -> bool {
+    let d = from_bytes(secret_key);
+    !is_zero(&d) && less_than(&d, &N.m) == 1
+}
+
+/// derives the compressed public key from a valid secret key
+pub fn secp256k1_public_key(public_key: &mut [u8], secret_key: &[u8]) {
+    let point = Point::generator().mul(&from_bytes(secret_key));
+    // the secret key is a valid scalar, so the point is never at infinity
+    public_key[..33].copy_from_slice(&point.encode().unwrap());
+}
+
+/// computes a HMAC-SHA256 over the concatenated parts
+fn hmac(key: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut state = HmacSha256State::new(key);
+    parts.iter().for_each(|p| state.update(p));
+    let mut mac = [0u8; 32];
+    state.finish(&mut mac);
+    mac
+}
+
+/// signs a 32 byte hash with a valid secret key using a RFC 6979 nonce. Writes the low-S signature `r || s` into
+/// `sig` and returns the recovery id.
+pub fn secp256k1_sign(sig: &mut [u8], hash: &[u8], secret_key: &[u8]) -> u8 {
+    let d = from_bytes(secret_key);
+    let z = N.reduce(&from_bytes(hash));
+    let h1 = to_bytes(&z);
+
+    // RFC 6979 section 3.2 with HMAC-SHA256
+    let (mut k_mac, mut v) = ([0u8; 32], [1u8; 32]);
+    k_mac = hmac(&k_mac, &[&v, &[0x00], secret_key, &h1]);
+    v = hmac(&k_mac, &[&v]);
+    k_mac = hmac(&k_mac, &[&v, &[0x01], secret_key, &h1]);
+    v = hmac(&k_mac, &[&v]);
+
+    loop {
+        v = hmac(&k_mac, &[&v]);
+        let k = from_bytes(&v);
+
+        if !is_zero(&k) && less_than(&k, &N.m) == 1 {
+            // R = k * G, r = R.x mod n
+            let (rx, ry) = Point::generator().mul(&k).to_affine().unwrap();
+            let r = N.reduce(&rx);
+            let mut recovery_id = or!(and!(ry[0], 1) as u8, shift_left!(less_than(&r, &rx) as u8, 1));
+
+            // s = k^-1 * (z + r * d) mod n
+            let (rm, dm, zm, km) = (N.to_mont(&r), N.to_mont(&d), N.to_mont(&z), N.to_mont(&k));
+            let sm = N.mul(&N.invert(&km), &N.add(&zm, &N.mul(&rm, &dm)));
+            let mut s = N.to_normal(&sm);
+
+            if !is_zero(&r) && !is_zero(&s) {
+                // normalize to the lower half of the order
+                let high = less_than(&N_HALF, &s);
+                s = select(&s, &N.sub(&[0; 4], &s), high);
+                recovery_id = xor!(recovery_id, high as u8);
+
+                sig[..32].copy_from_slice(&to_bytes(&r));
+                sig[32..64].copy_from_slice(&to_bytes(&s));
+                return recovery_id;
+            }
+        }
+
+        k_mac = hmac(&k_mac, &[&v, &[0x00]]);
+        v = hmac(&k_mac, &[&v]);
+    }
+}
+
+/// decodes the signature values (r, s) and rejects zero or unreduced values as well as malleable high s values
+fn signature_scalars(sig: &[u8]) -> Option<([u64; 4], [u64; 4])> {
+    let (r, s) = (from_bytes(&sig[..32]), from_bytes(&sig[32..64]));
+    if is_zero(&r) || is_zero(&s) || less_than(&r, &N.m) == 0 || less_than(&N_HALF, &s) == 1 {
+        return None;
+    }
+    Some((r, s))
+}
+
+/// verifies a low-S signature `r || s` over a 32 byte hash with a SEC1 encoded public key
+pub fn secp256k1_verify(sig: &[u8], hash: &[u8], public_key: &[u8]) -> bool {
+    let (r, s) = match signature_scalars(sig) {
+        Some(scalars) => scalars,
+        None => return false,
+    };
+    let q = match Point::decode(public_key) {
+        Some(q) => q,
+        None => return false,
+    };
+
+    // R = u1 * G + u2 * Q with u1 = z * s^-1 and u2 = r * s^-1
+    let z = N.to_mont(&N.reduce(&from_bytes(hash)));
+    let s_inv = N.invert(&N.to_mont(&s));
+    let u1 = N.to_normal(&N.mul(&z, &s_inv));
+    let u2 = N.to_normal(&N.mul(&N.to_mont(&r), &s_inv));
+    match Point::generator().mul(&u1).add(&q.mul(&u2)).to_affine() {
+        Some((x, _)) => N.reduce(&x) == r,
+        None => false,
+    }
+}
+
+/// recovers the compressed public key from a 32 byte hash and a recoverable signature `r || s || v`
+pub fn secp256k1_recover(public_key: &mut [u8], sig: &[u8], hash: &[u8]) -> bool {
+    let recovery_id = sig[64];
+    if recovery_id > 3 {
+        return false;
+    }
+    let (r, s) = match signature_scalars(sig) {
+        Some(scalars) => scalars,
+        None => return false,
+    };
+
+    // R.x is r, or r + n if the second bit of the recovery id is set
+    let mut x = r;
+    if and!(recovery_id, 2) != 0 {
+        let mut carry = 0;
+        (0..4).for_each(|i| {
+            let (v, c) = adc(r[i], N.m[i], carry);
+            x[i] = v;
+            carry = c;
+        });
+        if carry != 0 {
+            return false;
+        }
+    }
+    let big_r = match Point::from_x(&x, and!(recovery_id, 1) as u64) {
+        Some(point) => point,
+        None => return false,
+    };
+
+    // Q = r^-1 * (s * R - z * G)
+    let z = N.to_mont(&N.reduce(&from_bytes(hash)));
+    let r_inv = N.invert(&N.to_mont(&r));
+    let u1 = N.to_normal(&N.mul(&N.sub(&[0; 4], &z), &r_inv));
+    let u2 = N.to_normal(&N.mul(&N.to_mont(&s), &r_inv));
+    match Point::generator().mul(&u1).add(&big_r.mul(&u2)).encode() {
+        Some(encoded) => {
+            public_key[..33].copy_from_slice(&encoded);
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,314 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use std::cmp::min;
+
+/// SHA-256 round constants
+const K256: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5, 0xd807aa98,
+    0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786,
+    0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8,
+    0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13,
+    0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819,
+    0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a,
+    0x5b9cca4f, 0x682e6ff3, 0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+    0xc67178f2,
+];
+
+/// SHA-256 initial hash values
+const IV256: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// SHA-512 round constants
+const K512: [u64; 80] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
+/// SHA-512 initial hash values
+const IV512: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// runs the SHA-2 rounds over the state `h` with the message schedule `w`.
+macro_rules! sha2_rounds {
+    ($h:expr, $w:expr, $k:expr, $s0:expr, $s1:expr) => {{
+        let (mut a, mut b, mut c, mut d) = ($h[0], $h[1], $h[2], $h[3]);
+        let (mut e, mut f, mut g, mut hh) = ($h[4], $h[5], $h[6], $h[7]);
+
+        for i in 0..$k.len() {
+            let big_s1 = xor!(
+                xor!(rotate_right!(e, $s1[0]), rotate_right!(e, $s1[1])),
+                rotate_right!(e, $s1[2])
+            );
+            let ch = xor!(and!(e, f), and!(!e, g));
+            let t1 = add!(add!(hh, big_s1, ch), $k[i], $w[i]);
+            let big_s0 = xor!(
+                xor!(rotate_right!(a, $s0[0]), rotate_right!(a, $s0[1])),
+                rotate_right!(a, $s0[2])
+            );
+            let maj = xor!(xor!(and!(a, b), and!(a, c)), and!(b, c));
+            let t2 = add!(big_s0, maj);
+
+            hh = g;
+            g = f;
+            f = e;
+            e = add!(d, t1);
+            d = c;
+            c = b;
+            b = a;
+            a = add!(t1, t2);
+        }
+
+        $h[0] = add!($h[0], a);
+        $h[1] = add!($h[1], b);
+        $h[2] = add!($h[2], c);
+        $h[3] = add!($h[3], d);
+        $h[4] = add!($h[4], e);
+        $h[5] = add!($h[5], f);
+        $h[6] = add!($h[6], g);
+        $h[7] = add!($h[7], hh);
+    }};
+}
+
+/// compresses a 64 byte block into the SHA-256 state
+fn sha256_compress(h: &mut [u32; 8], block: &[u8]) {
+    // build the message schedule
+    let mut w = [0u32; 64];
+    (0..16).for_each(|i| w[i] = read32_big_endian!(&block[i * 4..]));
+    for i in 16..64 {
+        let s0 = xor!(
+            xor!(rotate_right!(w[i - 15], 7), rotate_right!(w[i - 15], 18)),
+            shift_right!(w[i - 15], 3)
+        );
+        let s1 = xor!(
+            xor!(rotate_right!(w[i - 2], 17), rotate_right!(w[i - 2], 19)),
+            shift_right!(w[i - 2], 10)
+        );
+        w[i] = add!(add!(w[i - 16], s0), add!(w[i - 7], s1));
+    }
+
+    sha2_rounds!(h, w, K256, [2, 13, 22], [6, 11, 25]);
+}
+
+/// compresses a 128 byte block into the SHA-512 state
+fn sha512_compress(h: &mut [u64; 8], block: &[u8]) {
+    // build the message schedule
+    let mut w = [0u64; 80];
+    (0..16).for_each(|i| w[i] = read64_big_endian!(&block[i * 8..]));
+    for i in 16..80 {
+        let s0 = xor!(
+            xor!(rotate_right!(w[i - 15], 1), rotate_right!(w[i - 15], 8)),
+            shift_right!(w[i - 15], 7)
+        );
+        let s1 = xor!(
+            xor!(rotate_right!(w[i - 2], 19), rotate_right!(w[i - 2], 61)),
+            shift_right!(w[i - 2], 6)
+        );
+        w[i] = add!(add!(w[i - 16], s0), add!(w[i - 7], s1));
+    }
+
+    sha2_rounds!(h, w, K512, [28, 34, 39], [14, 18, 41]);
+}
+
+/// buffers incoming data and feeds complete blocks into the compression function
+macro_rules! sha2_update {
+    ($self:expr, $data:expr, $compress:expr) => {{
+        let mut data = $data;
+        $self.len = add!($self.len, data.len() as u128);
+
+        while !data.is_empty() {
+            // fill the block buffer
+            let to_copy = min(data.len(), $self.buf.len() - $self.buf_len);
+            $self.buf[$self.buf_len..$self.buf_len + to_copy].copy_from_slice(&data[..to_copy]);
+            $self.buf_len += to_copy;
+            data = &data[to_copy..];
+
+            // compress a full block
+            if $self.buf_len == $self.buf.len() {
+                $compress(&mut $self.h, &$self.buf);
+                $self.buf_len = 0;
+            }
+        }
+    }};
+}
+
+/// pads the last block with the message length and compresses it
+macro_rules! sha2_finish {
+    ($self:expr, $compress:expr, $len_size:expr) => {{
+        let block_len = $self.buf.len();
+        let bit_len = mult!($self.len, 8);
+
+        // append the 0x80 byte and pad with zeros
+        $self.buf[$self.buf_len] = 0x80;
+        $self.buf[$self.buf_len + 1..].iter_mut().for_each(|b| *b = 0);
+        if $self.buf_len + 1 > block_len - $len_size {
+            $compress(&mut $self.h, &$self.buf);
+            $self.buf.iter_mut().for_each(|b| *b = 0);
+        }
+
+        // append the message length in bits
+        let len_bytes = bit_len.to_be_bytes();
+        $self.buf[block_len - $len_size..].copy_from_slice(&len_bytes[16 - $len_size..]);
+        $compress(&mut $self.h, &$self.buf);
+        $self.buf_len = 0;
+    }};
+}
+
+/// The running state of a SHA-256 computation
+#[derive(Clone)]
+pub struct Sha256State {
+    h: [u32; 8],
+    buf: [u8; 64],
+    buf_len: usize,
+    len: u128,
+}
+
+impl Sha256State {
+    /// creates a new SHA-256 state
+    pub fn new() -> Self {
+        Self {
+            h: IV256,
+            buf: [0; 64],
+            buf_len: 0,
+            len: 0,
+        }
+    }
+
+    /// absorbs data into the state
+    pub fn update(&mut self, data: &[u8]) {
+        sha2_update!(self, data, sha256_compress);
+    }
+
+    /// pads the message and writes the 32 byte digest into `buf`
+    pub fn finish(mut self, buf: &mut [u8]) {
+        sha2_finish!(self, sha256_compress, 8);
+        (0..8).for_each(|i| write32_big_endian!(self.h[i] => &mut buf[i * 4..]));
+    }
+}
+
+/// The running state of a SHA-512 computation
+#[derive(Clone)]
+pub struct Sha512State {
+    h: [u64; 8],
+    buf: [u8; 128],
+    buf_len: usize,
+    len: u128,
+}
+
+impl Sha512State {
+    /// creates a new SHA-512 state
+    pub fn new() -> Self {
+        Self {
+            h: IV512,
+            buf: [0; 128],
+            buf_len: 0,
+            len: 0,
+        }
+    }
+
+    /// absorbs data into the state
+    pub fn update(&mut self, data: &[u8]) {
+        sha2_update!(self, data, sha512_compress);
+    }
+
+    /// pads the message and writes the 64 byte digest into `buf`
+    pub fn finish(mut self, buf: &mut [u8]) {
+        sha2_finish!(self, sha512_compress, 16);
+        (0..8).for_each(|i| write64_big_endian!(self.h[i] => &mut buf[i * 8..]));
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::curve25519::Fe;
+
+/// the u coordinate of the base point
+pub const X25519_BASE: [u8; 32] = [
+    9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+/// computes the X25519 function of the 32 byte `scalar` and u coordinate `u` (RFC 7748) into `out`
+pub fn x25519(out: &mut [u8], scalar: &[u8], u: &[u8]) {
+    // clamp the scalar
+    let mut k = [0u8; 32];
+    k.copy_from_slice(&scalar[..32]);
+    k[0] = and!(k[0], 248);
+    k[31] = or!(and!(k[31], 127), 64);
+
+    // the Montgomery ladder
+    let x1 = Fe::from_bytes(u);
+    let (mut x2, mut z2, mut x3, mut z3) = (Fe::ONE, Fe::ZERO, x1, Fe::ONE);
+    let mut swap = 0;
+    for t in (0..255).rev() {
+        let k_t = and!(shift_right!(k[t / 8], (t % 8) as u32), 1) as u64;
+        swap = xor!(swap, k_t);
+        Fe::cswap(&mut x2, &mut x3, swap);
+        Fe::cswap(&mut z2, &mut z3, swap);
+        swap = k_t;
+
+        let a = x2.add(&z2);
+        let aa = a.square();
+        let b = x2.sub(&z2);
+        let bb = b.square();
+        let e = aa.sub(&bb);
+        let c = x3.add(&z3);
+        let d = x3.sub(&z3);
+        let da = d.mul(&a);
+        let cb = c.mul(&b);
+        x3 = da.add(&cb).square();
+        z3 = x1.mul(&da.sub(&cb).square());
+        x2 = aa.mul(&bb);
+        z2 = e.mul(&aa.add(&e.mul_small(121665)));
+    }
+    Fe::cswap(&mut x2, &mut x3, swap);
+    Fe::cswap(&mut z2, &mut z3, swap);
+
+    out[..32].copy_from_slice(&x2.mul(&z2.invert()).to_bytes());
+}
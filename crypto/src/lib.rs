@@ -11,42 +11,77 @@
 
 #![cfg_attr(feature = "cargo-clippy", allow(clippy::reversed_empty_ranges))]
 
-/// This crate implements five different cryptographically secure cipher algorithms:
-/// - Poly1305
-/// - ChaCha20
-/// - XChaCha20
-/// - ChaCha20-Poly1305
-/// - XChaCha20-Poly1305
-///
-/// The internals of these algorithms are defined using macros to make them compose with one another. The
-/// algorithms were tested against libsodium's algorithms to verify their integrity.
-use thiserror::Error as DeriveError;
+//! This crate implements seven different cryptographically secure cipher algorithms:
+//! - Poly1305
+//! - ChaCha20
+//! - XChaCha20
+//! - ChaCha20-Poly1305
+//! - XChaCha20-Poly1305
+//! - AES-256-GCM
+//! - AES-256-GCM-SIV
+//!
+//! XChaCha20-Poly1305 is also available as a chunked stream cipher using the STREAM construction.
+//!
+//! As well as the following hash functions:
+//! - SHA-256
+//! - SHA-512
+//! - BLAKE2b (also usable as a keyed MAC)
+//!
+//! And HMAC-SHA256 and HMAC-SHA512 message authentication codes, as well as the HKDF-SHA256 and HKDF-SHA512 key
+//! derivation functions and the PBKDF2-HMAC-SHA256, PBKDF2-HMAC-SHA512 and Argon2id password based key derivation
+//! functions.
+//!
+//! And the Ed25519 and secp256k1 ECDSA signature schemes and the X25519 key exchange.
+//!
+//! The ciphers, MACs and hashes can be looked up by the id of their info block through the `Registry`.
+//!
+//! The internals of these algorithms are defined using macros to make them compose with one another. The
+//! algorithms were tested against libsodium's algorithms to verify their integrity.
 
 #[macro_use]
 mod internal;
 #[macro_use]
 mod verify;
 
+mod aesgcm;
+mod aesgcmsiv;
+mod argon2;
+mod blake2b;
 mod chacha_ietf;
 mod chachapoly_ietf;
+mod ed25519;
+mod hkdf;
+mod hmac;
+mod pbkdf2;
 mod poly;
+mod registry;
+mod secp256k1;
+mod sha2;
+mod stream;
+mod x25519;
 mod xchacha;
 mod xchachapoly;
 
 pub use crate::{
-    chacha_ietf::ChaCha20Ietf, chachapoly_ietf::ChaChaPolyIetf, poly::Poly1305, xchacha::XChaCha20,
+    aesgcm::Aes256Gcm,
+    aesgcmsiv::Aes256GcmSiv,
+    argon2::Argon2id,
+    blake2b::Blake2b,
+    chacha_ietf::ChaCha20Ietf,
+    chachapoly_ietf::ChaChaPolyIetf,
+    ed25519::Ed25519,
+    hkdf::{HkdfSha256, HkdfSha512},
+    hmac::{HmacSha256, HmacSha512},
+    pbkdf2::{Pbkdf2Sha256, Pbkdf2Sha512},
+    poly::Poly1305,
+    registry::Registry,
+    secp256k1::Secp256k1,
+    sha2::{Sha256, Sha512},
+    stream::{StreamDecryptor, StreamEncryptor},
+    x25519::X25519,
+    xchacha::XChaCha20,
     xchachapoly::XChaChaPoly,
 };
-pub use primitives;
-
-#[derive(DeriveError, Debug)]
-pub enum Error {
-    #[error("Invalid Data")]
-    InvalidData,
-    #[error("Crypto Interface Error")]
-    InterfaceError,
-    #[error("Error: `{0}`")]
-    CryptoError(String),
-}
+pub use primitives::{self, Error};
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,116 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::Error;
+use crate::{
+    internal::pbkdf2::{pbkdf2_sha256, pbkdf2_sha512},
+    sha2::{SHA256_LEN, SHA512_LEN},
+};
+use primitives::pbkdf::{Pbkdf, PbkdfInfo};
+
+/// min size of the derived output
+pub const PBKDF2_OUTPUT_MIN: usize = 1;
+/// max size of any password or salt
+pub const PBKDF2_INPUT_MAX: usize = usize::MAX;
+/// min number of iterations
+pub const PBKDF2_ITERATIONS_MIN: u64 = 1;
+/// max number of iterations
+pub const PBKDF2_ITERATIONS_MAX: u64 = usize::MAX as u64;
+/// max size of the PBKDF2-HMAC-SHA256 output ((2^32 - 1) blocks)
+#[cfg(target_pointer_width = "64")]
+pub const PBKDF2_SHA256_OUTPUT_MAX: usize = u32::MAX as usize * SHA256_LEN;
+/// max size of the PBKDF2-HMAC-SHA256 output (capped at the addressable size)
+#[cfg(not(target_pointer_width = "64"))]
+pub const PBKDF2_SHA256_OUTPUT_MAX: usize = usize::MAX;
+/// default number of PBKDF2-HMAC-SHA256 iterations
+pub const PBKDF2_SHA256_ITERATIONS: u64 = 600_000;
+/// max size of the PBKDF2-HMAC-SHA512 output ((2^32 - 1) blocks)
+#[cfg(target_pointer_width = "64")]
+pub const PBKDF2_SHA512_OUTPUT_MAX: usize = u32::MAX as usize * SHA512_LEN;
+/// max size of the PBKDF2-HMAC-SHA512 output (capped at the addressable size)
+#[cfg(not(target_pointer_width = "64"))]
+pub const PBKDF2_SHA512_OUTPUT_MAX: usize = usize::MAX;
+/// default number of PBKDF2-HMAC-SHA512 iterations
+pub const PBKDF2_SHA512_ITERATIONS: u64 = 210_000;
+
+/// PBKDF2-HMAC-SHA256 password based key derivation function (RFC 8018)
+pub struct Pbkdf2Sha256;
+impl Pbkdf2Sha256 {
+    /// creates a PBKDF
+    pub fn pbkdf() -> Box<dyn Pbkdf> {
+        Box::new(Self)
+    }
+}
+
+impl Pbkdf for Pbkdf2Sha256 {
+    fn info(&self) -> PbkdfInfo {
+        PbkdfInfo {
+            id: "Pbkdf2Sha256",
+            output_lens: PBKDF2_OUTPUT_MIN..PBKDF2_SHA256_OUTPUT_MAX,
+            password_lens: 0..PBKDF2_INPUT_MAX,
+            salt_lens: 0..PBKDF2_INPUT_MAX,
+            cpu_cost: PBKDF2_SHA256_ITERATIONS,
+            cpu_costs: PBKDF2_ITERATIONS_MIN as usize..PBKDF2_ITERATIONS_MAX as usize,
+            memory_cost: 0,
+            memory_costs: 0..0,
+            parallelism: 0,
+            parallelisms: 0..0,
+        }
+    }
+
+    /// derives the key using `cpu_cost` as the number of iterations.
+    fn derive(&self, buf: &mut [u8], password: &[u8], salt: &[u8], cpu_cost: u64) -> Result<(), Error> {
+        verify_pbkdf!(
+            => [buf, PBKDF2_OUTPUT_MIN, PBKDF2_SHA256_OUTPUT_MAX],
+            cpu_cost => [PBKDF2_ITERATIONS_MIN, PBKDF2_ITERATIONS_MAX]
+        );
+
+        pbkdf2_sha256(buf, password, salt, cpu_cost);
+        Ok(())
+    }
+}
+
+/// PBKDF2-HMAC-SHA512 password based key derivation function (RFC 8018)
+pub struct Pbkdf2Sha512;
+impl Pbkdf2Sha512 {
+    /// creates a PBKDF
+    pub fn pbkdf() -> Box<dyn Pbkdf> {
+        Box::new(Self)
+    }
+}
+
+impl Pbkdf for Pbkdf2Sha512 {
+    fn info(&self) -> PbkdfInfo {
+        PbkdfInfo {
+            id: "Pbkdf2Sha512",
+            output_lens: PBKDF2_OUTPUT_MIN..PBKDF2_SHA512_OUTPUT_MAX,
+            password_lens: 0..PBKDF2_INPUT_MAX,
+            salt_lens: 0..PBKDF2_INPUT_MAX,
+            cpu_cost: PBKDF2_SHA512_ITERATIONS,
+            cpu_costs: PBKDF2_ITERATIONS_MIN as usize..PBKDF2_ITERATIONS_MAX as usize,
+            memory_cost: 0,
+            memory_costs: 0..0,
+            parallelism: 0,
+            parallelisms: 0..0,
+        }
+    }
+
+    /// derives the key using `cpu_cost` as the number of iterations.
+    fn derive(&self, buf: &mut [u8], password: &[u8], salt: &[u8], cpu_cost: u64) -> Result<(), Error> {
+        verify_pbkdf!(
+            => [buf, PBKDF2_OUTPUT_MIN, PBKDF2_SHA512_OUTPUT_MAX],
+            cpu_cost => [PBKDF2_ITERATIONS_MIN, PBKDF2_ITERATIONS_MAX]
+        );
+
+        pbkdf2_sha512(buf, password, salt, cpu_cost);
+        Ok(())
+    }
+}
@@ -9,14 +9,14 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-use crate::internal::poly::{poly1305_finish, poly1305_init, poly1305_update};
+use crate::internal::poly::Poly1305State;
 
 use primitives::{
-    auth::{MessageAuthCode, MessageAuthCodeInfo},
+    auth::{MacState, MessageAuthCode, MessageAuthCodeInfo},
     rng::{SecretKeyGen, SecureRng},
 };
 
-use std::error::Error;
+use crate::Error;
 
 /// Size of the Key
 pub const POLY1305_KEY: usize = 32;
@@ -31,20 +31,25 @@ impl Poly1305 {
         Box::new(Self)
     }
 
+    /// creates an incremental MAC using the one-time `key`
+    pub fn mac_state(key: &[u8]) -> Result<Box<dyn MacState>, Error> {
+        verify_auth!(key => [POLY1305_KEY]);
+
+        Ok(Box::new(Poly1305MacState(Some(Poly1305State::new(key)))))
+    }
+
     /// function to create ChaChaPoly-IETF AEAD
-    pub(in crate) fn chachapoly_auth(tag: &mut [u8], ad: &[u8], data: &[u8], foot: &[u8], key: &[u8]) {
-        let (mut r, mut s, mut u, mut a) = (vec![0; 5], vec![0; 4], vec![0; 5], vec![0; 5]);
-        poly1305_init(&mut r, &mut s, &mut u, key);
-
-        poly1305_update(&mut a, &r, &u, ad, false);
-        poly1305_update(&mut a, &r, &u, data, false);
-        poly1305_update(&mut a, &r, &u, foot, true);
-        poly1305_finish(tag, &mut a, &s);
+    pub(crate) fn chachapoly_auth(tag: &mut [u8], ad: &[u8], data: &[u8], foot: &[u8], key: &[u8]) {
+        let mut state = Poly1305State::new(key);
+        state.update_padded(ad);
+        state.update_padded(data);
+        state.update(foot);
+        state.finish(tag);
     }
 }
 
 impl SecretKeyGen for Poly1305 {
-    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Box<dyn Error + 'static>> {
+    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Error> {
         verify_keygen!(POLY1305_KEY => buf);
 
         rng.random(&mut buf[..POLY1305_KEY])?;
@@ -63,14 +68,34 @@ impl MessageAuthCode for Poly1305 {
         }
     }
 
-    fn auth(&self, buf: &mut [u8], data: &[u8], key: &[u8]) -> Result<usize, Box<dyn Error + 'static>> {
+    fn auth(&self, buf: &mut [u8], data: &[u8], key: &[u8]) -> Result<usize, Error> {
         verify_auth!(key => [POLY1305_KEY], => [buf, POLY1305_TAG]);
 
-        let (mut r, mut s, mut u, mut a) = (vec![0; 5], vec![0; 4], vec![0; 5], vec![0; 5]);
-        poly1305_init(&mut r, &mut s, &mut u, key);
-        poly1305_update(&mut a, &r, &u, data, true);
-        poly1305_finish(buf, &mut a, &s);
+        let mut state = Poly1305State::new(key);
+        state.update(data);
+        state.finish(buf);
+
+        Ok(POLY1305_TAG)
+    }
+}
+
+/// incremental Poly1305 MAC
+struct Poly1305MacState(Option<Poly1305State>);
+
+impl MacState for Poly1305MacState {
+    fn info(&self) -> MessageAuthCodeInfo {
+        MessageAuthCode::info(&Poly1305)
+    }
+
+    fn update(&mut self, data: &[u8]) -> Result<(), Error> {
+        verify_state!(self.0.as_mut()).update(data);
+        Ok(())
+    }
+
+    fn finalize(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        verify_hash!(=> [buf, POLY1305_TAG]);
 
+        verify_state!(self.0.take()).finish(buf);
         Ok(POLY1305_TAG)
     }
 }
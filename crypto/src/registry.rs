@@ -0,0 +1,106 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::{
+    Aes256Gcm, Aes256GcmSiv, Blake2b, ChaCha20Ietf, ChaChaPolyIetf, HmacSha256, HmacSha512, Poly1305, Sha256, Sha512,
+    XChaCha20, XChaChaPoly,
+};
+use primitives::{
+    auth::MessageAuthCode,
+    cipher::{AeadCipher, Cipher},
+    hash::Hash,
+};
+
+use crate::Error;
+
+/// constructors of the registered ciphers
+const CIPHERS: &[fn() -> Box<dyn Cipher>] = &[
+    ChaCha20Ietf::cipher,
+    XChaCha20::cipher,
+    ChaChaPolyIetf::cipher,
+    XChaChaPoly::cipher,
+    Aes256Gcm::cipher,
+    Aes256GcmSiv::cipher,
+];
+/// constructors of the registered AEAD ciphers
+const AEAD_CIPHERS: &[fn() -> Box<dyn AeadCipher>] = &[
+    ChaChaPolyIetf::aead_cipher,
+    XChaChaPoly::aead_cipher,
+    Aes256Gcm::aead_cipher,
+    Aes256GcmSiv::aead_cipher,
+];
+/// constructors of the registered MACs
+const MESSAGE_AUTH_CODES: &[fn() -> Box<dyn MessageAuthCode>] = &[
+    Poly1305::message_auth_code,
+    HmacSha256::message_auth_code,
+    HmacSha512::message_auth_code,
+    Blake2b::message_auth_code,
+];
+/// constructors of the registered hashes
+const HASHERS: &[fn() -> Box<dyn Hash>] = &[Sha256::hasher, Sha512::hasher, Blake2b::hasher];
+
+/// Looks up the algorithms of this crate by the id of their info block, so that data which records the id of the
+/// algorithm that produced it can be processed with the same algorithm later on.
+pub struct Registry;
+impl Registry {
+    /// creates the Cipher with the id `id`
+    pub fn cipher(id: &str) -> Result<Box<dyn Cipher>, Error> {
+        lookup(CIPHERS, id, |cipher| cipher.info().id)
+    }
+
+    /// creates the AEAD Cipher with the id `id`
+    pub fn aead_cipher(id: &str) -> Result<Box<dyn AeadCipher>, Error> {
+        lookup(AEAD_CIPHERS, id, |cipher| cipher.info().id)
+    }
+
+    /// creates the MAC with the id `id`
+    pub fn message_auth_code(id: &str) -> Result<Box<dyn MessageAuthCode>, Error> {
+        lookup(MESSAGE_AUTH_CODES, id, |mac| mac.info().id)
+    }
+
+    /// creates the Hash with the id `id`
+    pub fn hasher(id: &str) -> Result<Box<dyn Hash>, Error> {
+        lookup(HASHERS, id, |hash| hash.info().id)
+    }
+
+    /// the ids of all registered Ciphers
+    pub fn cipher_ids() -> Vec<&'static str> {
+        CIPHERS.iter().map(|new| new().info().id).collect()
+    }
+
+    /// the ids of all registered AEAD Ciphers
+    pub fn aead_cipher_ids() -> Vec<&'static str> {
+        AEAD_CIPHERS.iter().map(|new| new().info().id).collect()
+    }
+
+    /// the ids of all registered MACs
+    pub fn message_auth_code_ids() -> Vec<&'static str> {
+        MESSAGE_AUTH_CODES.iter().map(|new| new().info().id).collect()
+    }
+
+    /// the ids of all registered Hashes
+    pub fn hasher_ids() -> Vec<&'static str> {
+        HASHERS.iter().map(|new| new().info().id).collect()
+    }
+}
+
+/// finds the algorithm whose info block has the id `id`
+fn lookup<T: ?Sized>(
+    registered: &[fn() -> Box<T>],
+    id: &str,
+    info_id: impl Fn(&T) -> &'static str,
+) -> Result<Box<T>, Error> {
+    registered
+        .iter()
+        .map(|new| new())
+        .find(|algorithm| info_id(algorithm) == id)
+        .ok_or(crate::Error::UnknownAlgorithm)
+}
@@ -0,0 +1,149 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::secp256k1::{
+    secp256k1_public_key, secp256k1_recover, secp256k1_sign, secp256k1_valid_secret_key, secp256k1_verify, sha256,
+};
+use crate::Error;
+use primitives::{
+    rng::{PublicKeyGen, SecretKeyGen, SecureRng},
+    signing::{Sign, SignInfo},
+};
+
+/// size of the secret key
+pub const SECP256K1_SECRET_KEY: usize = 32;
+/// size of the compressed public key
+pub const SECP256K1_PUBLIC_KEY: usize = 33;
+/// size of the uncompressed public key
+pub const SECP256K1_PUBLIC_KEY_UNCOMPRESSED: usize = 65;
+/// size of the signature `r || s`
+pub const SECP256K1_SIGNATURE: usize = 64;
+/// size of the recoverable signature `r || s || v`
+pub const SECP256K1_RECOVERABLE_SIGNATURE: usize = 65;
+/// size of a prehashed message
+pub const SECP256K1_HASH: usize = 32;
+
+/// ECDSA over secp256k1 with deterministic RFC 6979 nonces and low-S signatures. The `Sign` implementation hashes
+/// the data with SHA-256, the `*_prehashed` functions, `sign_recoverable` and `recover` take a 32 byte hash.
+pub struct Secp256k1;
+impl Secp256k1 {
+    /// creates a signer
+    pub fn signer() -> Box<dyn Sign> {
+        Box::new(Self)
+    }
+
+    /// signs a 32 byte hash and returns the length of the signature `r || s`.
+    pub fn sign_prehashed(
+        &self,
+        buf: &mut [u8],
+        hash: &[u8; SECP256K1_HASH],
+        secret_key: &[u8],
+    ) -> Result<usize, Error> {
+        verify_sign!(secret_key => [SECP256K1_SECRET_KEY], => [buf, SECP256K1_SIGNATURE]);
+        Self::verify_secret_key(secret_key)?;
+
+        secp256k1_sign(buf, hash, secret_key);
+        Ok(SECP256K1_SIGNATURE)
+    }
+
+    /// verifies a signature `r || s` over a 32 byte hash. High-S signatures are rejected.
+    pub fn verify_prehashed(&self, hash: &[u8; SECP256K1_HASH], sig: &[u8], public_key: &[u8]) -> Result<(), Error> {
+        verify_sign!(
+            public_key => [SECP256K1_PUBLIC_KEY, or SECP256K1_PUBLIC_KEY_UNCOMPRESSED],
+            sig => [SECP256K1_SIGNATURE]
+        );
+
+        match secp256k1_verify(sig, hash, public_key) {
+            true => Ok(()),
+            false => Err(crate::Error::AuthenticationFailed),
+        }
+    }
+
+    /// signs a 32 byte hash and returns the length of the recoverable signature `r || s || v` where `v` is the
+    /// recovery id from 0 to 3.
+    pub fn sign_recoverable(
+        &self,
+        buf: &mut [u8],
+        hash: &[u8; SECP256K1_HASH],
+        secret_key: &[u8],
+    ) -> Result<usize, Error> {
+        verify_sign!(secret_key => [SECP256K1_SECRET_KEY], => [buf, SECP256K1_RECOVERABLE_SIGNATURE]);
+        Self::verify_secret_key(secret_key)?;
+
+        buf[SECP256K1_SIGNATURE] = secp256k1_sign(buf, hash, secret_key);
+        Ok(SECP256K1_RECOVERABLE_SIGNATURE)
+    }
+
+    /// recovers the compressed public key from a recoverable signature over a 32 byte hash and returns its length.
+    pub fn recover(&self, buf: &mut [u8], hash: &[u8; SECP256K1_HASH], sig: &[u8]) -> Result<usize, Error> {
+        verify_sign!(=> [buf, SECP256K1_PUBLIC_KEY], sig => [SECP256K1_RECOVERABLE_SIGNATURE]);
+
+        match secp256k1_recover(buf, sig, hash) {
+            true => Ok(SECP256K1_PUBLIC_KEY),
+            false => Err(crate::Error::AuthenticationFailed),
+        }
+    }
+
+    /// checks that the secret key is a scalar in 1..n
+    fn verify_secret_key(secret_key: &[u8]) -> crate::Result<()> {
+        match secp256k1_valid_secret_key(secret_key) {
+            true => Ok(()),
+            false => Err(crate::Error::InvalidSecretKey),
+        }
+    }
+}
+
+impl SecretKeyGen for Secp256k1 {
+    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Error> {
+        verify_keygen!(SECP256K1_SECRET_KEY => buf);
+
+        // draw until the key is a valid scalar, which fails with a probability of about 2^-128
+        loop {
+            rng.random(&mut buf[..SECP256K1_SECRET_KEY])?;
+            if secp256k1_valid_secret_key(buf) {
+                return Ok(SECP256K1_SECRET_KEY);
+            }
+        }
+    }
+}
+
+impl PublicKeyGen for Secp256k1 {
+    /// derives the compressed public key.
+    fn get_pub_key(&self, buf: &mut [u8], secret_key: &[u8]) -> Result<usize, Error> {
+        verify_sign!(secret_key => [SECP256K1_SECRET_KEY], => [buf, SECP256K1_PUBLIC_KEY]);
+        Self::verify_secret_key(secret_key)?;
+
+        secp256k1_public_key(buf, secret_key);
+        Ok(SECP256K1_PUBLIC_KEY)
+    }
+}
+
+impl Sign for Secp256k1 {
+    fn info(&self) -> SignInfo {
+        SignInfo {
+            id: "Secp256k1",
+            sig_lens: SECP256K1_SIGNATURE..SECP256K1_SIGNATURE,
+            secret_key_lens: SECP256K1_SECRET_KEY..SECP256K1_SECRET_KEY,
+            // only the compressed (33 bytes) and uncompressed (65 bytes) lengths are accepted, not the range between
+            public_key_lens: SECP256K1_PUBLIC_KEY..SECP256K1_PUBLIC_KEY_UNCOMPRESSED,
+        }
+    }
+
+    /// signs the SHA-256 hash of the data.
+    fn sign(&self, buf: &mut [u8], data: &[u8], secret_key: &[u8]) -> Result<usize, Error> {
+        self.sign_prehashed(buf, &sha256(data), secret_key)
+    }
+
+    /// verifies a signature over the SHA-256 hash of the data with a compressed or uncompressed public key.
+    fn verify(&self, data: &[u8], sig: &[u8], public_key: &[u8]) -> Result<(), Error> {
+        self.verify_prehashed(&sha256(data), sig, public_key)
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::sha2::{Sha256State, Sha512State};
+use crate::Error;
+use primitives::hash::{Hash, HashInfo, HashState};
+
+/// size of the SHA-256 digest
+pub const SHA256_LEN: usize = 32;
+/// size of the SHA-512 digest
+pub const SHA512_LEN: usize = 64;
+
+/// SHA-256 hash
+pub struct Sha256;
+impl Sha256 {
+    /// creates a new Hash with SHA-256
+    pub fn hasher() -> Box<dyn Hash> {
+        Box::new(Self)
+    }
+
+    /// creates a new incremental Hash with SHA-256
+    pub fn hash_state() -> Box<dyn HashState> {
+        Box::new(Sha256HashState(Some(Sha256State::new())))
+    }
+}
+
+impl Hash for Sha256 {
+    fn info(&self) -> HashInfo {
+        HashInfo {
+            id: "Sha256",
+            hash_len: SHA256_LEN,
+            hash_lens: SHA256_LEN..SHA256_LEN,
+        }
+    }
+
+    fn hash(&self, buf: &mut [u8], data: &[u8]) -> Result<usize, Error> {
+        verify_hash!(=> [buf, SHA256_LEN]);
+
+        let mut state = Sha256State::new();
+        state.update(data);
+        state.finish(&mut buf[..SHA256_LEN]);
+        Ok(SHA256_LEN)
+    }
+}
+
+/// SHA-512 hash
+pub struct Sha512;
+impl Sha512 {
+    /// creates a new Hash with SHA-512
+    pub fn hasher() -> Box<dyn Hash> {
+        Box::new(Self)
+    }
+
+    /// creates a new incremental Hash with SHA-512
+    pub fn hash_state() -> Box<dyn HashState> {
+        Box::new(Sha512HashState(Some(Sha512State::new())))
+    }
+}
+
+impl Hash for Sha512 {
+    fn info(&self) -> HashInfo {
+        HashInfo {
+            id: "Sha512",
+            hash_len: SHA512_LEN,
+            hash_lens: SHA512_LEN..SHA512_LEN,
+        }
+    }
+
+    fn hash(&self, buf: &mut [u8], data: &[u8]) -> Result<usize, Error> {
+        verify_hash!(=> [buf, SHA512_LEN]);
+
+        let mut state = Sha512State::new();
+        state.update(data);
+        state.finish(&mut buf[..SHA512_LEN]);
+        Ok(SHA512_LEN)
+    }
+}
+
+/// incremental SHA-256 hash
+struct Sha256HashState(Option<Sha256State>);
+
+impl HashState for Sha256HashState {
+    fn info(&self) -> HashInfo {
+        Hash::info(&Sha256)
+    }
+
+    fn update(&mut self, data: &[u8]) -> Result<(), Error> {
+        verify_state!(self.0.as_mut()).update(data);
+        Ok(())
+    }
+
+    fn finalize(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        verify_hash!(=> [buf, SHA256_LEN]);
+
+        verify_state!(self.0.take()).finish(&mut buf[..SHA256_LEN]);
+        Ok(SHA256_LEN)
+    }
+}
+
+/// incremental SHA-512 hash
+struct Sha512HashState(Option<Sha512State>);
+
+impl HashState for Sha512HashState {
+    fn info(&self) -> HashInfo {
+        Hash::info(&Sha512)
+    }
+
+    fn update(&mut self, data: &[u8]) -> Result<(), Error> {
+        verify_state!(self.0.as_mut()).update(data);
+        Ok(())
+    }
+
+    fn finalize(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        verify_hash!(=> [buf, SHA512_LEN]);
+
+        verify_state!(self.0.take()).finish(&mut buf[..SHA512_LEN]);
+        Ok(SHA512_LEN)
+    }
+}
@@ -0,0 +1,279 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::xchachapoly::{xchachapoly_open, xchachapoly_seal, XCHACHAPOLY_KEY, XCHACHAPOLY_NONCE, XCHACHAPOLY_TAG};
+use primitives::rng::SecureRng;
+
+use std::{
+    cmp::min,
+    io::{self, ErrorKind, Read, Write},
+};
+
+/// size of the plaintext chunks
+pub const STREAM_CHUNK: usize = 65536;
+/// size of the random header that prefixes every chunk nonce
+pub const STREAM_HEADER: usize = 19;
+/// size of a sealed chunk
+pub const STREAM_SEALED_CHUNK: usize = STREAM_CHUNK + XCHACHAPOLY_TAG;
+
+/// derives the nonce of a chunk: `header || counter (big endian) || last chunk flag`
+fn chunk_nonce(header: &[u8; STREAM_HEADER], counter: u32, last: bool) -> [u8; XCHACHAPOLY_NONCE] {
+    let mut nonce = [0; XCHACHAPOLY_NONCE];
+    nonce[..STREAM_HEADER].copy_from_slice(header);
+    nonce[STREAM_HEADER..STREAM_HEADER + 4].copy_from_slice(&counter.to_be_bytes());
+    nonce[XCHACHAPOLY_NONCE - 1] = last as u8;
+    nonce
+}
+
+/// reads until `buf` is full or the reader is exhausted and returns the amount of bytes read.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match reader.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(read) => len += read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(len)
+}
+
+/// zeroes the whole capacity of a chunk buffer, including bytes beyond its length
+fn wipe(buf: &mut Vec<u8>) {
+    let capacity = buf.capacity();
+    buf.clear();
+    buf.resize(capacity, 0);
+}
+
+/// Encrypts a stream of data in chunks with XChaCha20-Poly1305 using the STREAM construction.
+///
+/// A random header is written first, followed by the sealed chunks. Every chunk nonce is derived from the header,
+/// the chunk counter and a flag marking the final chunk, so chunks can't be reordered, dropped or truncated without
+/// the decryption failing. `finish` must be called to seal the final chunk.
+pub struct StreamEncryptor<W: Write> {
+    // only taken by `finish`, because the encryptor can't be moved out of
+    writer: Option<W>,
+    key: [u8; XCHACHAPOLY_KEY],
+    header: [u8; STREAM_HEADER],
+    counter: u32,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> StreamEncryptor<W> {
+    /// creates a new encryptor with a random header from `rng` and writes the header to the `writer`
+    pub fn new(mut writer: W, key: &[u8], rng: &mut dyn SecureRng) -> io::Result<Self> {
+        verify_auth!(key => [XCHACHAPOLY_KEY]);
+
+        let mut header = [0; STREAM_HEADER];
+        rng.random(&mut header)?;
+        writer.write_all(&header)?;
+
+        let mut this = Self {
+            writer: Some(writer),
+            key: [0; XCHACHAPOLY_KEY],
+            header,
+            counter: 0,
+            buf: Vec::with_capacity(STREAM_SEALED_CHUNK),
+        };
+        this.key.copy_from_slice(key);
+        Ok(this)
+    }
+
+    /// seals the buffered data as the final chunk, flushes and returns the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.seal_chunk(true)?;
+        let mut writer = self.writer.take().expect("the writer is only taken by `finish`");
+        writer.flush()?;
+        Ok(writer)
+    }
+
+    /// seals the buffered data as the next chunk and writes it
+    fn seal_chunk(&mut self, last: bool) -> io::Result<()> {
+        // the final chunk may use the last counter value but no chunk can follow it
+        if !last && self.counter == u32::MAX {
+            return Err(io::Error::new(ErrorKind::Other, "Stream is too long"));
+        }
+
+        let nonce = chunk_nonce(&self.header, self.counter, last);
+        let len = self.buf.len();
+        self.buf.resize(len + XCHACHAPOLY_TAG, 0);
+        let (data, tag) = self.buf.split_at_mut(len);
+        xchachapoly_seal(data, tag, &[], &self.key, &nonce);
+        let writer = self.writer.as_mut().expect("the writer is only taken by `finish`");
+        writer.write_all(&self.buf)?;
+
+        self.buf.clear();
+        self.counter = self.counter.wrapping_add(1);
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for StreamEncryptor<W> {
+    /// buffers the data and seals a chunk once it is full. A full chunk is only sealed when more data follows,
+    /// because the final chunk is marked when `finish` is called.
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if self.buf.len() == STREAM_CHUNK && !data.is_empty() {
+            self.seal_chunk(false)?;
+        }
+
+        let len = min(data.len(), STREAM_CHUNK - self.buf.len());
+        self.buf.extend_from_slice(&data[..len]);
+        Ok(len)
+    }
+
+    /// flushes the underlying writer. Buffered data of an incomplete chunk is not written.
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer
+            .as_mut()
+            .expect("the writer is only taken by `finish`")
+            .flush()
+    }
+}
+
+impl<W: Write> Drop for StreamEncryptor<W> {
+    fn drop(&mut self) {
+        wipe(&mut self.buf);
+        self.key.iter_mut().for_each(|b| *b = 0);
+    }
+}
+
+/// Decrypts a stream created by the `StreamEncryptor`.
+///
+/// Reading returns an `InvalidData` error if a chunk fails to authenticate or the stream was truncated, and the
+/// decryptor refuses to read any further after such an error. The end of the stream is only reported after the
+/// final chunk was authenticated.
+pub struct StreamDecryptor<R: Read> {
+    // only taken by `into_inner`, because the decryptor can't be moved out of
+    reader: Option<R>,
+    key: [u8; XCHACHAPOLY_KEY],
+    header: [u8; STREAM_HEADER],
+    counter: u32,
+    buf: Vec<u8>,
+    pos: usize,
+    peek: Option<u8>,
+    done: bool,
+    failed: bool,
+}
+
+impl<R: Read> StreamDecryptor<R> {
+    /// creates a new decryptor and reads the header from the `reader`
+    pub fn new(mut reader: R, key: &[u8]) -> io::Result<Self> {
+        verify_auth!(key => [XCHACHAPOLY_KEY]);
+
+        let mut header = [0; STREAM_HEADER];
+        if read_full(&mut reader, &mut header)? != STREAM_HEADER {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                crate::Error::AuthenticationFailed,
+            ));
+        }
+
+        let mut this = Self {
+            reader: Some(reader),
+            key: [0; XCHACHAPOLY_KEY],
+            header,
+            counter: 0,
+            buf: Vec::with_capacity(STREAM_SEALED_CHUNK),
+            pos: 0,
+            peek: None,
+            done: false,
+            failed: false,
+        };
+        this.key.copy_from_slice(key);
+        Ok(this)
+    }
+
+    /// returns the underlying reader
+    pub fn into_inner(mut self) -> R {
+        self.reader.take().expect("the reader is only taken by `into_inner`")
+    }
+
+    /// reads and opens the next chunk into the buffer
+    fn open_chunk(&mut self) -> io::Result<()> {
+        self.buf.resize(STREAM_SEALED_CHUNK, 0);
+        self.pos = 0;
+
+        // the byte read ahead to detect the final chunk belongs to this chunk
+        let reader = self.reader.as_mut().expect("the reader is only taken by `into_inner`");
+        let mut len = 0;
+        if let Some(byte) = self.peek.take() {
+            self.buf[0] = byte;
+            len = 1;
+        }
+        len += read_full(reader, &mut self.buf[len..])?;
+
+        // a full chunk is only the final one if nothing follows it
+        let last = if len == STREAM_SEALED_CHUNK {
+            let mut byte = [0];
+            match read_full(reader, &mut byte)? {
+                0 => true,
+                _ => {
+                    self.peek = Some(byte[0]);
+                    false
+                }
+            }
+        } else {
+            true
+        };
+
+        if len < XCHACHAPOLY_TAG || (!last && self.counter == u32::MAX) {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                crate::Error::AuthenticationFailed,
+            ));
+        }
+
+        let nonce = chunk_nonce(&self.header, self.counter, last);
+        let (data, tag) = self.buf[..len].split_at_mut(len - XCHACHAPOLY_TAG);
+        xchachapoly_open(data, tag, &[], &self.key, &nonce)
+            .map_err(|_| io::Error::new(ErrorKind::InvalidData, crate::Error::AuthenticationFailed))?;
+
+        self.buf.truncate(len - XCHACHAPOLY_TAG);
+        self.counter = self.counter.wrapping_add(1);
+        self.done = last;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for StreamDecryptor<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.failed {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                crate::Error::AuthenticationFailed,
+            ));
+        }
+
+        while self.pos == self.buf.len() {
+            if self.done {
+                return Ok(0);
+            }
+            if let Err(e) = self.open_chunk() {
+                self.buf.clear();
+                self.failed = true;
+                return Err(e);
+            }
+        }
+
+        let len = min(buf.len(), self.buf.len() - self.pos);
+        buf[..len].copy_from_slice(&self.buf[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+impl<R: Read> Drop for StreamDecryptor<R> {
+    fn drop(&mut self) {
+        wipe(&mut self.buf);
+        self.key.iter_mut().for_each(|b| *b = 0);
+    }
+}
@@ -39,30 +39,308 @@ macro_rules! verify_keygen {
         use $crate::verify::{SliceExt, USizeExt};
 
         let error = if $buf.constrain_value() != $size {
-            Err("Invalid buffer size")
+            Err($crate::Error::InvalidBufferSize)
         } else {
             Ok(())
         };
 
-        error.map_err(|e| $crate::Error::CryptoError(e.into()))?;
+        error?;
     }};
 }
 
 /// verify auth parameters
 #[macro_export]
 macro_rules! verify_auth {
+    ($key:expr => [$key_min:expr, $key_max:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $key.constrain_value() < $key_min || $key.constrain_value() > $key_max {
+            Err($crate::Error::InvalidKeyLength)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+    ($key:expr => [$key_size:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $key.constrain_value() != $key_size {
+            Err($crate::Error::InvalidKeyLength)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+    (=> [$buf:expr, $tag_size:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $buf.constrain_value() < $tag_size {
+            Err($crate::Error::BufferTooSmall)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+    (=> [$buf:expr, $tag_min:expr, $tag_max:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $buf.constrain_value() < $tag_min {
+            Err($crate::Error::BufferTooSmall)
+        } else if $buf.constrain_value() > $tag_max {
+            Err($crate::Error::BufferTooLarge)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
     ($key:expr => [$key_size:expr], => [$buf:expr, $tag_size:expr]) => {{
         #[allow(unused_imports)]
         use $crate::verify::{SliceExt, USizeExt};
 
         let error = if $key.constrain_value() != $key_size {
-            Err("Invalid key length")
+            Err($crate::Error::InvalidKeyLength)
         } else if $buf.constrain_value() < $tag_size {
-            Err("Buffer is too small")
+            Err($crate::Error::BufferTooSmall)
         } else {
             Ok(())
         };
-        error.map_err(|e| $crate::Error::CryptoError(e.into()))?;
+        error?;
+    }};
+    ($key:expr => [$key_min:expr, $key_max:expr], => [$buf:expr, $tag_size:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $key.constrain_value() < $key_min || $key.constrain_value() > $key_max {
+            Err($crate::Error::InvalidKeyLength)
+        } else if $buf.constrain_value() < $tag_size {
+            Err($crate::Error::BufferTooSmall)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+    ($key:expr => [$key_min:expr, $key_max:expr], => [$buf:expr, $tag_min:expr, $tag_max:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $key.constrain_value() < $key_min || $key.constrain_value() > $key_max {
+            Err($crate::Error::InvalidKeyLength)
+        } else if $buf.constrain_value() < $tag_min {
+            Err($crate::Error::BufferTooSmall)
+        } else if $buf.constrain_value() > $tag_max {
+            Err($crate::Error::BufferTooLarge)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+}
+/// verify hash parameters
+#[macro_export]
+macro_rules! verify_hash {
+    (=> [$buf:expr, $hash_size:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $buf.constrain_value() < $hash_size {
+            Err($crate::Error::BufferTooSmall)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+    (=> [$buf:expr, $hash_min:expr, $hash_max:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $buf.constrain_value() < $hash_min {
+            Err($crate::Error::BufferTooSmall)
+        } else if $buf.constrain_value() > $hash_max {
+            Err($crate::Error::BufferTooLarge)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+}
+/// verify key derivation parameters
+#[macro_export]
+macro_rules! verify_kdf {
+    (=> [$buf:expr, $out_size:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $buf.constrain_value() < $out_size {
+            Err($crate::Error::BufferTooSmall)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+    (=> [$buf:expr, $out_min:expr, $out_max:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $buf.constrain_value() < $out_min {
+            Err($crate::Error::BufferTooSmall)
+        } else if $buf.constrain_value() > $out_max {
+            Err($crate::Error::BufferTooLarge)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+    ($key:expr => [>= $key_min:expr], => [$buf:expr, $out_min:expr, $out_max:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $key.constrain_value() < $key_min {
+            Err($crate::Error::InvalidKeyLength)
+        } else if $buf.constrain_value() < $out_min {
+            Err($crate::Error::BufferTooSmall)
+        } else if $buf.constrain_value() > $out_max {
+            Err($crate::Error::BufferTooLarge)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+}
+/// verify password based key derivation parameters
+#[macro_export]
+macro_rules! verify_pbkdf {
+    (=> [$buf:expr, $out_min:expr, $out_max:expr], $cpu_cost:expr => [$cpu_min:expr, $cpu_max:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $buf.constrain_value() < $out_min {
+            Err($crate::Error::BufferTooSmall)
+        } else if $buf.constrain_value() > $out_max {
+            Err($crate::Error::BufferTooLarge)
+        } else if !($cpu_min..=$cpu_max).contains(&$cpu_cost) {
+            Err($crate::Error::InvalidCpuCost)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+    (=> [$buf:expr, $out_min:expr, $out_max:expr], [$($input:expr),+] => [$input_max:expr],
+        $salt:expr => [$salt_min:expr, $salt_max:expr], $cpu_cost:expr => [$cpu_min:expr, $cpu_max:expr],
+        $parallelism:expr => [$par_min:expr, $par_max:expr], $memory_cost:expr => [$mem_min:expr, $mem_max:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $buf.constrain_value() < $out_min {
+            Err($crate::Error::BufferTooSmall)
+        } else if $buf.constrain_value() > $out_max {
+            Err($crate::Error::BufferTooLarge)
+        } else if [$($input.constrain_value()),+].iter().any(|len| *len > $input_max) {
+            Err($crate::Error::InvalidInputLength)
+        } else if !($salt_min..=$salt_max).contains(&$salt.constrain_value()) {
+            Err($crate::Error::InvalidSaltLength)
+        } else if !($cpu_min..=$cpu_max).contains(&$cpu_cost) {
+            Err($crate::Error::InvalidCpuCost)
+        } else if !($par_min..=$par_max).contains(&$parallelism) {
+            Err($crate::Error::InvalidParallelism)
+        } else if !($mem_min..=$mem_max).contains(&$memory_cost) {
+            Err($crate::Error::InvalidMemoryCost)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+}
+/// verify signing parameters
+#[macro_export]
+macro_rules! verify_sign {
+    ($key:expr => [$key_size:expr], => [$buf:expr, $out_size:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $key.constrain_value() != $key_size {
+            Err($crate::Error::InvalidKeyLength)
+        } else if $buf.constrain_value() < $out_size {
+            Err($crate::Error::BufferTooSmall)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+    ($key:expr => [$key_size:expr], $sig:expr => [$sig_size:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $key.constrain_value() != $key_size {
+            Err($crate::Error::InvalidKeyLength)
+        } else if $sig.constrain_value() != $sig_size {
+            Err($crate::Error::InvalidSignatureLength)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+    ($key:expr => [$key_size_a:expr, or $key_size_b:expr], $sig:expr => [$sig_size:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $key.constrain_value() != $key_size_a && $key.constrain_value() != $key_size_b {
+            Err($crate::Error::InvalidKeyLength)
+        } else if $sig.constrain_value() != $sig_size {
+            Err($crate::Error::InvalidSignatureLength)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+    (=> [$buf:expr, $out_size:expr], $sig:expr => [$sig_size:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $sig.constrain_value() != $sig_size {
+            Err($crate::Error::InvalidSignatureLength)
+        } else if $buf.constrain_value() < $out_size {
+            Err($crate::Error::BufferTooSmall)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+}
+/// verify key exchange parameters
+#[macro_export]
+macro_rules! verify_exchange {
+    ($secret_key:expr => [$secret_size:expr], => [$buf:expr, $out_size:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $secret_key.constrain_value() != $secret_size {
+            Err($crate::Error::InvalidKeyLength)
+        } else if $buf.constrain_value() < $out_size {
+            Err($crate::Error::BufferTooSmall)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+    ($secret_key:expr => [$secret_size:expr], $public_key:expr => [$public_size:expr],
+		=> [$buf:expr, $out_size:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $secret_key.constrain_value() != $secret_size {
+            Err($crate::Error::InvalidKeyLength)
+        } else if $public_key.constrain_value() != $public_size {
+            Err($crate::Error::InvalidPublicKeyLength)
+        } else if $buf.constrain_value() < $out_size {
+            Err($crate::Error::BufferTooSmall)
+        } else {
+            Ok(())
+        };
+        error?;
     }};
 }
 /// verify encryption parameters
@@ -74,17 +352,17 @@ macro_rules! verify_encrypt {
         use $crate::verify::{SliceExt, USizeExt};
 
         let error = if $key.constrain_value() != $key_size {
-            Err("Invalid key length")
+            Err($crate::Error::InvalidKeyLength)
         } else if $nonce.constrain_value() != $nonce_size {
-            Err("Invalid nonce length")
+            Err($crate::Error::InvalidNonceLength)
         } else if $plain.constrain_value() > $plain_limit {
-            Err("Too much data")
+            Err($crate::Error::TooMuchData)
         } else if $plain.constrain_value() > $buf.constrain_value() {
-            Err("Buffer is too small")
+            Err($crate::Error::BufferTooSmall)
         } else {
             Ok(())
         };
-        error.map_err(|e| $crate::Error::CryptoError(e.into()))?;
+        error?;
     }};
 }
 /// verify decryption parameters
@@ -96,40 +374,58 @@ macro_rules! verify_decrypt {
         use $crate::verify::{SliceExt, USizeExt};
 
         let error = if $key.constrain_value() != $key_size {
-            Err("Invalid key length")
+            Err($crate::Error::InvalidKeyLength)
         } else if $nonce.constrain_value() != $nonce_size {
-            Err("Invalid nonce length")
+            Err($crate::Error::InvalidNonceLength)
         } else if $cipher.constrain_value() > $cipher_limit {
-            Err("Too much data")
+            Err($crate::Error::TooMuchData)
         } else if $cipher.constrain_value() > $buf.constrain_value() {
-            Err("Buffer is too small")
+            Err($crate::Error::BufferTooSmall)
         } else {
             Ok(())
         };
-        error.map_err(|e| $crate::Error::CryptoError(e.into()))?;
+        error?;
     }};
 }
 
 /// verify seal parameters
 #[macro_export]
 macro_rules! verify_seal {
+    ($key:expr => [$key_size:expr], $nonce:expr => [$nonce_size:expr],
+		$plain:expr => [$buf:expr, $tag_size:expr, $plain_limit:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $key.constrain_value() != $key_size {
+            Err($crate::Error::InvalidKeyLength)
+        } else if $nonce.constrain_value() != $nonce_size {
+            Err($crate::Error::InvalidNonceLength)
+        } else if $plain.constrain_value() > $plain_limit {
+            Err($crate::Error::TooMuchData)
+        } else if $buf.constrain_value() < $plain.constrain_value() + $tag_size {
+            Err($crate::Error::BufferTooSmall)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
     ($key:expr => [$key_size:expr], $nonce:expr => [$nonce_const:expr],
 		$plain:expr => [$buf:expr, $plain_limit:expr]) => {{
         #[allow(unused_imports)]
         use $crate::verify::{SliceExt, USizeExt};
 
         let error = if $key.constrain_value() != $key_size {
-            Err("Invalid key length")
+            Err($crate::Error::InvalidKeyLength)
         } else if $nonce.constrain_value() != $nonce_const {
-            Err("Invalid nonce length")
+            Err($crate::Error::InvalidNonceLength)
         } else if $plain.constrain_value() > $plain_limit {
-            Err("Too much data")
+            Err($crate::Error::TooMuchData)
         } else if $buf.constrain_value() < $plain.constrain_value() + CHACHAPOLY_TAG {
-            Err("Buffer is too small")
+            Err($crate::Error::BufferTooSmall)
         } else {
             Ok(())
         };
-        error.map_err(|e| $crate::Error::CryptoError(e.into()))?;
+        error?;
     }};
 }
 /// verify opening in place parameters
@@ -141,18 +437,69 @@ macro_rules! verify_open {
         use $crate::verify::{SliceExt, USizeExt};
 
         let error = if $key.constrain_value() != $key_size {
-            Err("Invalid key length")
+            Err($crate::Error::InvalidKeyLength)
         } else if $nonce.constrain_value() != $nonce_size {
-            Err("Invalid nonce length")
+            Err($crate::Error::InvalidNonceLength)
         } else if $cipher.constrain_value() > $cipher_limit {
-            Err("Too much data")
+            Err($crate::Error::TooMuchData)
         } else if $cipher.constrain_value() < $tag_size {
-            return Err($crate::Error::InvalidData.into());
+            return Err($crate::Error::AuthenticationFailed);
         } else if $buf.constrain_value() + $tag_size < $cipher.constrain_value() {
-            Err("Buffer is too small")
+            Err($crate::Error::BufferTooSmall)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+}
+/// verify detached sealing parameters
+#[macro_export]
+macro_rules! verify_seal_detached {
+    ($key:expr => [$key_size:expr], $nonce:expr => [$nonce_size:expr],
+		$data:expr => [$data_limit:expr], $tag:expr => [$tag_size:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $key.constrain_value() != $key_size {
+            Err($crate::Error::InvalidKeyLength)
+        } else if $nonce.constrain_value() != $nonce_size {
+            Err($crate::Error::InvalidNonceLength)
+        } else if $data.constrain_value() > $data_limit {
+            Err($crate::Error::TooMuchData)
+        } else if $tag.constrain_value() < $tag_size {
+            Err($crate::Error::BufferTooSmall)
         } else {
             Ok(())
         };
-        error.map_err(|e| $crate::Error::CryptoError(e.into()))?;
+        error?;
+    }};
+}
+/// verify detached opening parameters
+#[macro_export]
+macro_rules! verify_open_detached {
+    ($key:expr => [$key_size:expr], $nonce:expr => [$nonce_size:expr],
+		$data:expr => [$data_limit:expr], $tag:expr => [$tag_size:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::verify::{SliceExt, USizeExt};
+
+        let error = if $key.constrain_value() != $key_size {
+            Err($crate::Error::InvalidKeyLength)
+        } else if $nonce.constrain_value() != $nonce_size {
+            Err($crate::Error::InvalidNonceLength)
+        } else if $data.constrain_value() > $data_limit {
+            Err($crate::Error::TooMuchData)
+        } else if $tag.constrain_value() != $tag_size {
+            Err($crate::Error::AuthenticationFailed)
+        } else {
+            Ok(())
+        };
+        error?;
+    }};
+}
+/// verify that an incremental state was not finalized yet and unwrap it
+#[macro_export]
+macro_rules! verify_state {
+    ($state:expr) => {{
+        $state.ok_or($crate::Error::StateFinalized)?
     }};
 }
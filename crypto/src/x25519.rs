@@ -0,0 +1,76 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::internal::x25519::{x25519, X25519_BASE};
+use crate::Error;
+use primitives::{
+    key_exchange::{KeyExchange, KeyExchangeInfo},
+    rng::{PublicKeyGen, SecretKeyGen, SecureRng},
+};
+
+/// size of the secret key
+pub const X25519_SECRET_KEY: usize = 32;
+/// size of the public key
+pub const X25519_PUBLIC_KEY: usize = 32;
+/// size of the shared secret
+pub const X25519_SHARED_SECRET: usize = 32;
+
+/// X25519 Diffie-Hellman key agreement (RFC 7748)
+pub struct X25519;
+impl X25519 {
+    /// creates a key exchange
+    pub fn key_exchange() -> Box<dyn KeyExchange> {
+        Box::new(Self)
+    }
+}
+
+impl SecretKeyGen for X25519 {
+    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Error> {
+        verify_keygen!(X25519_SECRET_KEY => buf);
+
+        rng.random(&mut buf[..X25519_SECRET_KEY])?;
+        Ok(X25519_SECRET_KEY)
+    }
+}
+
+impl PublicKeyGen for X25519 {
+    fn get_pub_key(&self, buf: &mut [u8], secret_key: &[u8]) -> Result<usize, Error> {
+        verify_exchange!(secret_key => [X25519_SECRET_KEY], => [buf, X25519_PUBLIC_KEY]);
+
+        x25519(buf, secret_key, &X25519_BASE);
+        Ok(X25519_PUBLIC_KEY)
+    }
+}
+
+impl KeyExchange for X25519 {
+    fn info(&self) -> KeyExchangeInfo {
+        KeyExchangeInfo {
+            id: "X25519",
+            secret_key_lens: X25519_SECRET_KEY..X25519_SECRET_KEY,
+            public_key_lens: X25519_PUBLIC_KEY..X25519_PUBLIC_KEY,
+            shared_secret_lens: X25519_SHARED_SECRET..X25519_SHARED_SECRET,
+        }
+    }
+
+    /// computes the shared secret and rejects public keys of small order, which result in an all zero secret.
+    fn exchange(&self, buf: &mut [u8], secret_key: &[u8], public_key: &[u8]) -> Result<usize, Error> {
+        verify_exchange!(
+            secret_key => [X25519_SECRET_KEY], public_key => [X25519_PUBLIC_KEY],
+            => [buf, X25519_SHARED_SECRET]
+        );
+
+        x25519(buf, secret_key, public_key);
+        if eq_const_time!(&buf[..X25519_SHARED_SECRET], &[0; X25519_SHARED_SECRET]) {
+            return Err(crate::Error::InvalidPublicKey);
+        }
+        Ok(X25519_SHARED_SECRET)
+    }
+}
@@ -9,15 +9,16 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
+use crate::Error;
 use crate::{
     chacha_ietf::CHACHA20_KEY,
-    internal::chacha::{chacha20_block, h_chacha20_hash},
+    internal::chacha::{chacha20_xor, h_chacha20_hash},
 };
 use primitives::{
     cipher::{Cipher, CipherInfo},
     rng::{SecretKeyGen, SecureRng},
 };
-use std::{cmp::min, error::Error};
+use std::convert::TryFrom;
 
 /// max bytes that can be processed with a key/nonce combo
 pub const XCHACHA20_MAX: usize = usize::max_value();
@@ -34,34 +35,29 @@ impl XChaCha20 {
     }
 
     /// Xor the bytes with XChaCha20 kestream.
-    pub fn xor(key: &[u8], nonce: &[u8], mut n: u64, mut data: &mut [u8]) {
+    pub fn xor(key: &[u8], nonce: &[u8], n: u64, data: &mut [u8]) {
         // check input
         assert_eq!(XCHACHA20_KEY, key.len());
         assert_eq!(XCHACHA20_NONCE, nonce.len());
 
+        // verify that the block counter doesn't overflow
+        let blocks = (data.len() + 63) / 64;
+        u64::try_from(blocks)
+            .ok()
+            .and_then(|blocks| n.checked_add(blocks))
+            .expect("The ChaCha20 block counter must not exceed 2^64 - 1");
+
         // derive key
         let (x_nonce, nonce) = nonce.split_at(16);
         let mut x_key = vec![0; 32];
         h_chacha20_hash(key, x_nonce, &mut x_key);
 
         // XOR data
-        let mut buf = vec![0; 64];
-        while !data.is_empty() {
-            // Calculate next block
-            chacha20_block(&x_key, nonce, n, &mut buf);
-            n = n
-                .checked_add(1)
-                .expect("The ChaCha20 block counter must not exceed 2^64 - 1");
-
-            // XOR blocks
-            let to_xor = min(data.len(), buf.len());
-            (0..to_xor).for_each(|i| data[i] = xor!(data[i], buf[i]));
-            data = &mut data[to_xor..];
-        }
+        chacha20_xor(&x_key, nonce, n, data);
     }
 }
 impl SecretKeyGen for XChaCha20 {
-    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Box<dyn Error + 'static>> {
+    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Error> {
         verify_keygen!(XCHACHA20_KEY => buf);
 
         rng.random(&mut buf[..XCHACHA20_KEY])?;
@@ -84,13 +80,7 @@ impl Cipher for XChaCha20 {
         plain_len
     }
 
-    fn encrypt(
-        &self,
-        buf: &mut [u8],
-        plain_len: usize,
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn encrypt(&self, buf: &mut [u8], plain_len: usize, key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_encrypt!(
             key => [XCHACHA20_KEY], nonce => [XCHACHA20_NONCE],
             plain_len => [buf, XCHACHA20_MAX]
@@ -99,13 +89,7 @@ impl Cipher for XChaCha20 {
         Self::xor(key, nonce, 0, &mut buf[..plain_len]);
         Ok(plain_len)
     }
-    fn encrypt_to(
-        &self,
-        buf: &mut [u8],
-        plain: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn encrypt_to(&self, buf: &mut [u8], plain: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_encrypt!(
             key => [XCHACHA20_KEY], nonce => [XCHACHA20_NONCE],
             plain => [buf, XCHACHA20_MAX]
@@ -116,13 +100,7 @@ impl Cipher for XChaCha20 {
         Ok(plain.len())
     }
 
-    fn decrypt(
-        &self,
-        buf: &mut [u8],
-        cipher_len: usize,
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn decrypt(&self, buf: &mut [u8], cipher_len: usize, key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_decrypt!(
             key => [XCHACHA20_KEY], nonce => [XCHACHA20_NONCE],
             cipher_len => [buf, XCHACHA20_MAX]
@@ -131,13 +109,7 @@ impl Cipher for XChaCha20 {
         Self::xor(key, nonce, 0, &mut buf[..cipher_len]);
         Ok(cipher_len)
     }
-    fn decrypt_to(
-        &self,
-        buf: &mut [u8],
-        cipher: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn decrypt_to(&self, buf: &mut [u8], cipher: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_decrypt!(
             key => [XCHACHA20_KEY], nonce => [XCHACHA20_NONCE],
             cipher => [buf, XCHACHA20_MAX]
@@ -15,11 +15,11 @@ use crate::{
     xchacha::XChaCha20,
 };
 use primitives::{
-    cipher::{AeadCipher, Cipher, CipherInfo},
+    cipher::{AeadCipher, Cipher, CipherInfo, DetachedAeadCipher},
     rng::{SecretKeyGen, SecureRng},
 };
 
-use std::error::Error;
+use crate::Error;
 
 /// max bytes that can be processed with a key/nonce combo
 #[allow(unused)]
@@ -32,7 +32,7 @@ pub const XCHACHAPOLY_NONCE: usize = 24;
 pub const XCHACHAPOLY_TAG: usize = CHACHAPOLY_TAG;
 
 /// encrypts data in-place and authenticates it
-fn xchachapoly_seal(data: &mut [u8], tag: &mut [u8], ad: &[u8], key: &[u8], nonce: &[u8]) {
+pub(in crate) fn xchachapoly_seal(data: &mut [u8], tag: &mut [u8], ad: &[u8], key: &[u8], nonce: &[u8]) {
     // xor and encrypt the data.
     XChaCha20::xor(key, nonce, 1, data);
 
@@ -48,13 +48,13 @@ fn xchachapoly_seal(data: &mut [u8], tag: &mut [u8], ad: &[u8], key: &[u8], nonc
 }
 
 /// decrypts data in-place after validation
-fn xchachapoly_open(
+pub(in crate) fn xchachapoly_open(
     data: &mut [u8],
     tag: &[u8],
     ad: &[u8],
     key: &[u8],
     nonce: &[u8],
-) -> Result<(), Box<dyn Error + 'static>> {
+) -> Result<(), Error> {
     // build footer
     let mut foot = Vec::with_capacity(16);
     foot.extend_from_slice(&(ad.len() as u64).to_le_bytes());
@@ -67,7 +67,7 @@ fn xchachapoly_open(
 
     // validate the tags.
     if !eq_const_time!(&tag, &verify_tag) {
-        return Err(crate::Error::InvalidData.into());
+        return Err(crate::Error::AuthenticationFailed);
     }
     XChaCha20::xor(key, nonce, 1, data);
     Ok(())
@@ -86,10 +86,14 @@ impl XChaChaPoly {
     pub fn aead_cipher() -> Box<dyn AeadCipher> {
         Box::new(Self)
     }
+    // builds a detached tag AEAD Cipher with XChaChaPolyIETF
+    pub fn detached_aead_cipher() -> Box<dyn DetachedAeadCipher> {
+        Box::new(Self)
+    }
 }
 impl SecretKeyGen for XChaChaPoly {
     /// generate a new secret key
-    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Box<dyn Error + 'static>> {
+    fn new_secret_key(&self, buf: &mut [u8], rng: &mut dyn SecureRng) -> Result<usize, Error> {
         // validate input
         verify_keygen!(XCHACHAPOLY_KEY => buf);
 
@@ -114,53 +118,22 @@ impl Cipher for XChaChaPoly {
         plain_len + 16
     }
 
-    fn encrypt(
-        &self,
-        buf: &mut [u8],
-        plain_len: usize,
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn encrypt(&self, buf: &mut [u8], plain_len: usize, key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         self.seal(buf, plain_len, &[], key, nonce)
     }
-    fn encrypt_to(
-        &self,
-        buf: &mut [u8],
-        plain: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn encrypt_to(&self, buf: &mut [u8], plain: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         self.seal_with(buf, plain, &[], key, nonce)
     }
 
-    fn decrypt(
-        &self,
-        buf: &mut [u8],
-        cipher_len: usize,
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn decrypt(&self, buf: &mut [u8], cipher_len: usize, key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         self.open(buf, cipher_len, &[], key, nonce)
     }
-    fn decrypt_to(
-        &self,
-        buf: &mut [u8],
-        cipher: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn decrypt_to(&self, buf: &mut [u8], cipher: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         self.open_to(buf, cipher, &[], key, nonce)
     }
 }
 impl AeadCipher for XChaChaPoly {
-    fn seal(
-        &self,
-        buf: &mut [u8],
-        plain_len: usize,
-        ad: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn seal(&self, buf: &mut [u8], plain_len: usize, ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_seal!(
             key => [XCHACHAPOLY_KEY], nonce => [XCHACHAPOLY_NONCE],
             plain_len => [buf, XCHACHAPOLY_MAX]
@@ -170,14 +143,7 @@ impl AeadCipher for XChaChaPoly {
         xchachapoly_seal(data, &mut tag[..XCHACHAPOLY_TAG], ad, key, nonce);
         Ok(plain_len + XCHACHAPOLY_TAG)
     }
-    fn seal_with(
-        &self,
-        buf: &mut [u8],
-        plain: &[u8],
-        ad: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn seal_with(&self, buf: &mut [u8], plain: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_seal!(
             key => [XCHACHAPOLY_KEY], nonce => [XCHACHAPOLY_NONCE],
             plain => [buf, XCHACHAPOLY_MAX]
@@ -189,14 +155,7 @@ impl AeadCipher for XChaChaPoly {
         Ok(plain.len() + XCHACHAPOLY_TAG)
     }
 
-    fn open(
-        &self,
-        buf: &mut [u8],
-        cipher_len: usize,
-        ad: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn open(&self, buf: &mut [u8], cipher_len: usize, ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_open!(
             key => [XCHACHAPOLY_KEY], nonce => [XCHACHAPOLY_NONCE],
             cipher_len => [buf, XCHACHAPOLY_TAG, XCHACHAPOLY_MAX]
@@ -206,14 +165,7 @@ impl AeadCipher for XChaChaPoly {
         xchachapoly_open(data, &tag[..XCHACHAPOLY_TAG], ad, key, nonce)?;
         Ok(cipher_len - XCHACHAPOLY_TAG)
     }
-    fn open_to(
-        &self,
-        buf: &mut [u8],
-        cipher: &[u8],
-        ad: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>> {
+    fn open_to(&self, buf: &mut [u8], cipher: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
         verify_open!(
             key => [XCHACHAPOLY_KEY], nonce => [XCHACHAPOLY_NONCE],
             cipher => [buf, XCHACHAPOLY_TAG, XCHACHAPOLY_MAX]
@@ -225,3 +177,31 @@ impl AeadCipher for XChaChaPoly {
         Ok(cipher.len() - XCHACHAPOLY_TAG)
     }
 }
+impl DetachedAeadCipher for XChaChaPoly {
+    fn seal_detached(
+        &self,
+        data: &mut [u8],
+        tag: &mut [u8],
+        ad: &[u8],
+        key: &[u8],
+        nonce: &[u8],
+    ) -> Result<usize, Error> {
+        verify_seal_detached!(
+            key => [XCHACHAPOLY_KEY], nonce => [XCHACHAPOLY_NONCE],
+            data => [XCHACHAPOLY_MAX], tag => [XCHACHAPOLY_TAG]
+        );
+
+        xchachapoly_seal(data, &mut tag[..XCHACHAPOLY_TAG], ad, key, nonce);
+        Ok(XCHACHAPOLY_TAG)
+    }
+
+    fn open_detached(&self, data: &mut [u8], tag: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error> {
+        verify_open_detached!(
+            key => [XCHACHAPOLY_KEY], nonce => [XCHACHAPOLY_NONCE],
+            data => [XCHACHAPOLY_MAX], tag => [XCHACHAPOLY_TAG]
+        );
+
+        xchachapoly_open(data, tag, ad, key, nonce)?;
+        Ok(data.len())
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod common;
+
+use common::{JsonValueExt, ResultExt};
+use crypto::Argon2id;
+use json::JsonValue;
+
+// vector data.
+const VECTORS: &str = include_str!("argon2.json");
+
+// struct for vector data
+#[derive(Debug)]
+struct TestVector {
+    id: String,
+    password: Vec<u8>,
+    salt: Vec<u8>,
+    secret: Vec<u8>,
+    ad: Vec<u8>,
+    cpu_cost: u64,
+    memory_cost: u64,
+    parallelism: u64,
+    key: Vec<u8>,
+}
+
+impl TestVector {
+    // load json vectors
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut vecs = Vec::new();
+        for vec in json["crypto"].check_array_iter() {
+            vecs.push(Self {
+                id: vec["id"].check_string(),
+                password: vec["password"].check_bytes(),
+                salt: vec["salt"].check_bytes(),
+                secret: vec["secret"].check_bytes(),
+                ad: vec["ad"].check_bytes(),
+                cpu_cost: vec["cpu_cost"].as_u64().unwrap(),
+                memory_cost: vec["memory_cost"].as_u64().unwrap(),
+                parallelism: vec["parallelism"].as_u64().unwrap(),
+                key: vec["key"].check_bytes(),
+            });
+        }
+        vecs
+    }
+
+    // test the keyed key derivation
+    pub fn test_keyed(&self) -> &Self {
+        let mut buf = vec![0; self.key.len()];
+        Argon2id
+            .derive_keyed(
+                &mut buf,
+                &self.password,
+                &self.salt,
+                &self.secret,
+                &self.ad,
+                self.cpu_cost,
+                self.memory_cost,
+                self.parallelism,
+            )
+            .unwrap();
+        assert_eq!(buf, self.key, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // test the stateless key derivation
+    pub fn test_stateless(&self) -> &Self {
+        if !self.secret.is_empty() || !self.ad.is_empty() {
+            return self;
+        }
+
+        let mut buf = vec![0; self.key.len()];
+        Argon2id::stateless_pbkdf()
+            .derive_stateless(
+                &mut buf,
+                &self.password,
+                &self.salt,
+                self.cpu_cost,
+                self.memory_cost,
+                self.parallelism,
+            )
+            .unwrap();
+        assert_eq!(buf, self.key, "Vector: \"{}\"", self.id);
+
+        self
+    }
+}
+
+#[test]
+fn test_crypto() {
+    for vec in TestVector::load() {
+        vec.test_keyed().test_stateless();
+    }
+}
+
+// API test vector
+#[derive(Default, Clone, Debug)]
+pub struct ApiTestVector {
+    id: String,
+    password_len: usize,
+    salt_len: usize,
+    cpu_cost: u64,
+    memory_cost: u64,
+    parallelism: u64,
+    buf_len: usize,
+    error: String,
+}
+
+impl ApiTestVector {
+    // load json
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut defaults = Self::default();
+        defaults.load_json(&json["api"]["defaults"]);
+
+        let mut vecs = Vec::new();
+        for vec in json["api"]["tests"].members() {
+            let mut this = defaults.clone();
+            this.load_json(vec);
+            vecs.push(this);
+        }
+        vecs
+    }
+
+    // test the parameter validation
+    pub fn test_api(&self) -> &Self {
+        let password = vec![0; self.password_len];
+        let salt = vec![0; self.salt_len];
+        let mut buf = vec![0; self.buf_len];
+
+        let error = Argon2id::stateless_pbkdf()
+            .derive_stateless(
+                &mut buf,
+                &password,
+                &salt,
+                self.cpu_cost,
+                self.memory_cost,
+                self.parallelism,
+            )
+            .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), self.error, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // load json
+    fn load_json(&mut self, j: &JsonValue) {
+        self.id = j["id"].option_string(&self.id);
+        self.password_len = j["password_len"].option_usize(self.password_len);
+        self.salt_len = j["salt_len"].option_usize(self.salt_len);
+        self.cpu_cost = j["cpu_cost"].as_u64().unwrap_or(self.cpu_cost);
+        self.memory_cost = j["memory_cost"].as_u64().unwrap_or(self.memory_cost);
+        self.parallelism = j["parallelism"].as_u64().unwrap_or(self.parallelism);
+        self.buf_len = j["buf_len"].option_usize(self.buf_len);
+        self.error = j["error"].option_string(&self.error);
+    }
+}
+#[test]
+fn test_api() {
+    for vec in ApiTestVector::load() {
+        vec.test_api();
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod common;
+
+use common::{JsonValueExt, ResultExt};
+use crypto::Blake2b;
+use json::JsonValue;
+
+// vector data.
+const VECTORS: &str = include_str!("blake2b.json");
+// chunk sizes for the incremental hashes
+const CHUNKS: [usize; 4] = [1, 13, 128, 129];
+
+// struct for vector data
+#[derive(Debug)]
+struct TestVector {
+    id: String,
+    key: Vec<u8>,
+    data: Vec<u8>,
+    hash: Vec<u8>,
+}
+
+impl TestVector {
+    // load json vectors
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut vecs = Vec::new();
+        for vec in json["crypto"].check_array_iter() {
+            vecs.push(Self {
+                id: vec["id"].check_string(),
+                key: vec["key"].check_bytes(),
+                data: vec["data"].check_bytes(),
+                hash: vec["hash"].check_bytes(),
+            });
+        }
+        vecs
+    }
+
+    // test unkeyed hash calculation
+    pub fn test_hash(&self) -> &Self {
+        if !self.key.is_empty() {
+            return self;
+        }
+
+        let mut buf = vec![0; self.hash.len()];
+        let len = Blake2b::var_len_hasher().var_len_hash(&mut buf, &self.data).unwrap();
+        assert_eq!(buf[..len], self.hash[..], "Vector: \"{}\"", self.id);
+
+        if self.hash.len() == 64 {
+            let mut buf = vec![0; 64];
+            Blake2b::hasher().hash(&mut buf, &self.data).unwrap();
+            assert_eq!(buf, self.hash, "Vector: \"{}\"", self.id);
+
+            for &chunk in CHUNKS.iter() {
+                let mut state = Blake2b::hash_state();
+                self.data.chunks(chunk).for_each(|c| state.update(c).unwrap());
+                let mut buf = vec![0; 64];
+                state.finalize(&mut buf).unwrap();
+                assert_eq!(buf, self.hash, "Vector: \"{}\"", self.id);
+            }
+        }
+
+        self
+    }
+
+    // test keyed mac calculation
+    pub fn test_mac(&self) -> &Self {
+        if self.key.is_empty() {
+            return self;
+        }
+
+        let mut buf = vec![0; self.hash.len()];
+        let len = Blake2b::var_len_message_auth_code()
+            .varlen_auth(&mut buf, &self.data, &self.key)
+            .unwrap();
+        assert_eq!(buf[..len], self.hash[..], "Vector: \"{}\"", self.id);
+
+        if self.hash.len() == 64 {
+            let mut buf = vec![0; 64];
+            Blake2b::message_auth_code()
+                .auth(&mut buf, &self.data, &self.key)
+                .unwrap();
+            assert_eq!(buf, self.hash, "Vector: \"{}\"", self.id);
+
+            for &chunk in CHUNKS.iter() {
+                let mut state = Blake2b::mac_state(&self.key).unwrap();
+                self.data.chunks(chunk).for_each(|c| state.update(c).unwrap());
+                let mut buf = vec![0; 64];
+                state.finalize(&mut buf).unwrap();
+                assert_eq!(buf, self.hash, "Vector: \"{}\"", self.id);
+            }
+        }
+
+        self
+    }
+}
+
+#[test]
+fn test_crypto() {
+    for vec in TestVector::load() {
+        vec.test_hash().test_mac();
+    }
+}
+
+// API test vector
+#[derive(Default, Clone, Debug)]
+pub struct ApiTestVector {
+    id: String,
+    mode: String,
+    key_len: usize,
+    data_len: usize,
+    buf_len: usize,
+    error: String,
+}
+
+impl ApiTestVector {
+    // load json
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut defaults = Self::default();
+        defaults.load_json(&json["api"]["defaults"]);
+
+        let mut vecs = Vec::new();
+        for vec in json["api"]["tests"].members() {
+            let mut this = defaults.clone();
+            this.load_json(vec);
+            vecs.push(this);
+        }
+        vecs
+    }
+
+    // test the parameter validation
+    pub fn test_api(&self) -> &Self {
+        let key = vec![0; self.key_len];
+        let data = vec![0; self.data_len];
+        let mut buf = vec![0; self.buf_len];
+
+        let result = match self.mode.as_str() {
+            "hash" => Blake2b::hasher().hash(&mut buf, &data),
+            "var_len_hash" => Blake2b::var_len_hasher().var_len_hash(&mut buf, &data),
+            "auth" => Blake2b::message_auth_code().auth(&mut buf, &data, &key),
+            "varlen_auth" => Blake2b::var_len_message_auth_code().varlen_auth(&mut buf, &data, &key),
+            "hash_state" => {
+                let mut state = Blake2b::hash_state();
+                state.update(&data).and_then(|_| state.finalize(&mut buf))
+            }
+            "mac_state" => Blake2b::mac_state(&key)
+                .and_then(|mut state| state.update(&data).and_then(|_| state.finalize(&mut buf))),
+            _ => panic!("Vector: \"{}\"", self.id),
+        };
+        let error = result.error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), self.error, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // load json
+    fn load_json(&mut self, j: &JsonValue) {
+        self.id = j["id"].option_string(&self.id);
+        self.mode = j["mode"].option_string(&self.mode);
+        self.key_len = j["key_len"].option_usize(self.key_len);
+        self.data_len = j["data_len"].option_usize(self.data_len);
+        self.buf_len = j["buf_len"].option_usize(self.buf_len);
+        self.error = j["error"].option_string(&self.error);
+    }
+}
+#[test]
+fn test_api() {
+    for vec in ApiTestVector::load() {
+        vec.test_api();
+    }
+}
@@ -90,6 +90,57 @@ fn test_crypto() {
     }
 }
 
+// struct for keystream vectors that start at a block counter
+#[derive(Debug)]
+struct XorTestVector {
+    id: String,
+    key: Vec<u8>,
+    nonce: Vec<u8>,
+    block: u32,
+    plain: Vec<u8>,
+    cipher: Vec<u8>,
+}
+
+impl XorTestVector {
+    // load the json vectors
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut vecs = Vec::new();
+
+        for vec in json["xor"].check_array_iter() {
+            vecs.push(Self {
+                id: vec["id"].check_string(),
+                key: vec["key"].check_bytes(),
+                nonce: vec["nonce"].check_bytes(),
+                block: vec["block"].check_string().parse().unwrap(),
+                plain: vec["plain"].check_bytes(),
+                cipher: vec["cipher"].check_bytes(),
+            });
+        }
+
+        vecs
+    }
+
+    // test the keystream from the block counter
+    pub fn test_xor(&self) -> &Self {
+        let mut buf = self.plain.clone();
+        ChaCha20Ietf::xor(&self.key, &self.nonce, self.block, &mut buf);
+        assert_eq!(buf, self.cipher, "Vector: \"{}\"", self.id);
+
+        ChaCha20Ietf::xor(&self.key, &self.nonce, self.block, &mut buf);
+        assert_eq!(buf, self.plain, "Vector: \"{}\"", self.id);
+
+        self
+    }
+}
+
+#[test]
+fn test_xor() {
+    for vec in XorTestVector::load() {
+        vec.test_xor();
+    }
+}
+
 // API test vector
 #[derive(Default, Clone, Debug)]
 pub struct ApiTestVector {
@@ -0,0 +1,183 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod common;
+
+use common::{JsonValueExt, ResultExt};
+use crypto::{HkdfSha256, HkdfSha512};
+use json::JsonValue;
+
+// vector data.
+const VECTORS: &str = include_str!("hkdf.json");
+
+// struct for vector data
+#[derive(Debug)]
+struct TestVector {
+    id: String,
+    hash: String,
+    ikm: Vec<u8>,
+    salt: Vec<u8>,
+    info: Vec<u8>,
+    prk: Vec<u8>,
+    okm: Vec<u8>,
+}
+
+impl TestVector {
+    // load json vectors
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut vecs = Vec::new();
+        for vec in json["crypto"].check_array_iter() {
+            vecs.push(Self {
+                id: vec["id"].check_string(),
+                hash: vec["hash"].check_string(),
+                ikm: vec["ikm"].check_bytes(),
+                salt: vec["salt"].check_bytes(),
+                info: vec["info"].check_bytes(),
+                prk: vec["prk"].check_bytes(),
+                okm: vec["okm"].check_bytes(),
+            });
+        }
+        vecs
+    }
+
+    // test the extract step
+    pub fn test_extract(&self) -> &Self {
+        let mut buf = vec![0; self.prk.len()];
+        let len = match self.hash.as_str() {
+            "sha256" => HkdfSha256.extract(&mut buf, &self.salt, &self.ikm),
+            "sha512" => HkdfSha512.extract(&mut buf, &self.salt, &self.ikm),
+            _ => panic!("Vector: \"{}\"", self.id),
+        }
+        .unwrap();
+        assert_eq!(buf[..len], self.prk[..], "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // test the expand step
+    pub fn test_expand(&self) -> &Self {
+        let mut buf = vec![0; self.okm.len()];
+        match self.hash.as_str() {
+            "sha256" => HkdfSha256.expand(&mut buf, &self.prk, &self.info),
+            "sha512" => HkdfSha512.expand(&mut buf, &self.prk, &self.info),
+            _ => panic!("Vector: \"{}\"", self.id),
+        }
+        .unwrap();
+        assert_eq!(buf, self.okm, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // test the combined derivation
+    pub fn test_derive(&self) -> &Self {
+        let mut buf = vec![0; self.okm.len()];
+        match self.hash.as_str() {
+            "sha256" => HkdfSha256::key_derv_func().derive(&mut buf, &self.ikm, &self.salt, &self.info),
+            "sha512" => HkdfSha512::key_derv_func().derive(&mut buf, &self.ikm, &self.salt, &self.info),
+            _ => panic!("Vector: \"{}\"", self.id),
+        }
+        .unwrap();
+        assert_eq!(buf, self.okm, "Vector: \"{}\"", self.id);
+
+        self
+    }
+}
+
+#[test]
+fn test_crypto() {
+    for vec in TestVector::load() {
+        vec.test_extract().test_expand().test_derive();
+    }
+}
+
+// API test vector
+#[derive(Default, Clone, Debug)]
+pub struct ApiTestVector {
+    id: String,
+    mode: String,
+    ikm_len: usize,
+    salt_len: usize,
+    info_len: usize,
+    prk_len_256: usize,
+    prk_len_512: usize,
+    buf_len_256: usize,
+    buf_len_512: usize,
+    error: String,
+}
+
+impl ApiTestVector {
+    // load json
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut defaults = Self::default();
+        defaults.load_json(&json["api"]["defaults"]);
+
+        let mut vecs = Vec::new();
+        for vec in json["api"]["tests"].members() {
+            let mut this = defaults.clone();
+            this.load_json(vec);
+            vecs.push(this);
+        }
+        vecs
+    }
+
+    // test the parameter validation
+    pub fn test_api(&self) -> &Self {
+        let ikm = vec![0; self.ikm_len];
+        let salt = vec![0; self.salt_len];
+        let info = vec![0; self.info_len];
+
+        let prk = vec![0; self.prk_len_256];
+        let mut buf = vec![0; self.buf_len_256];
+        let error = match self.mode.as_str() {
+            "derive" => HkdfSha256::key_derv_func().derive(&mut buf, &ikm, &salt, &info),
+            "extract" => HkdfSha256.extract(&mut buf, &salt, &ikm).map(|_| ()),
+            "expand" => HkdfSha256.expand(&mut buf, &prk, &info),
+            _ => panic!("Vector: \"{}\"", self.id),
+        }
+        .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), self.error, "Vector: \"{}\"", self.id);
+
+        let prk = vec![0; self.prk_len_512];
+        let mut buf = vec![0; self.buf_len_512];
+        let error = match self.mode.as_str() {
+            "derive" => HkdfSha512::key_derv_func().derive(&mut buf, &ikm, &salt, &info),
+            "extract" => HkdfSha512.extract(&mut buf, &salt, &ikm).map(|_| ()),
+            "expand" => HkdfSha512.expand(&mut buf, &prk, &info),
+            _ => panic!("Vector: \"{}\"", self.id),
+        }
+        .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), self.error, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // load json
+    fn load_json(&mut self, j: &JsonValue) {
+        self.id = j["id"].option_string(&self.id);
+        self.mode = j["mode"].option_string(&self.mode);
+        self.ikm_len = j["ikm_len"].option_usize(self.ikm_len);
+        self.salt_len = j["salt_len"].option_usize(self.salt_len);
+        self.info_len = j["info_len"].option_usize(self.info_len);
+        self.prk_len_256 = j["prk_len_256"].option_usize(self.prk_len_256);
+        self.prk_len_512 = j["prk_len_512"].option_usize(self.prk_len_512);
+        self.buf_len_256 = j["buf_len_256"].option_usize(self.buf_len_256);
+        self.buf_len_512 = j["buf_len_512"].option_usize(self.buf_len_512);
+        self.error = j["error"].option_string(&self.error);
+    }
+}
+#[test]
+fn test_api() {
+    for vec in ApiTestVector::load() {
+        vec.test_api();
+    }
+}
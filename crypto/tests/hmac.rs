@@ -0,0 +1,213 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod common;
+
+use common::{JsonValueExt, ResultExt};
+use crypto::{HmacSha256, HmacSha512};
+use json::JsonValue;
+
+// vector data.
+const VECTORS: &str = include_str!("hmac.json");
+// chunk sizes for the incremental MACs
+const CHUNKS: [usize; 4] = [1, 13, 64, 129];
+
+// struct for vector data
+#[derive(Debug)]
+struct TestVector {
+    id: String,
+    key: Vec<u8>,
+    data: Vec<u8>,
+    sha256: Vec<u8>,
+    sha512: Vec<u8>,
+}
+
+impl TestVector {
+    // load json vectors
+    pub fn load(section: &str) -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut vecs = Vec::new();
+        for vec in json[section].check_array_iter() {
+            vecs.push(Self {
+                id: vec["id"].check_string(),
+                key: vec["key"].check_bytes(),
+                data: vec["data"].check_bytes(),
+                sha256: vec["sha256"].check_bytes(),
+                sha512: vec["sha512"].check_bytes(),
+            });
+        }
+        vecs
+    }
+
+    // test mac calculation
+    pub fn test_mac(&self) -> &Self {
+        let mut buf = vec![0; self.sha256.len()];
+        HmacSha256::var_len_message_auth_code()
+            .varlen_auth(&mut buf, &self.data, &self.key)
+            .unwrap();
+        assert_eq!(buf, self.sha256, "Vector: \"{}\"", self.id);
+
+        let mut buf = vec![0; self.sha512.len()];
+        HmacSha512::var_len_message_auth_code()
+            .varlen_auth(&mut buf, &self.data, &self.key)
+            .unwrap();
+        assert_eq!(buf, self.sha512, "Vector: \"{}\"", self.id);
+
+        if self.sha256.len() == 32 {
+            let mut buf = vec![0; 32];
+            HmacSha256::message_auth_code()
+                .auth(&mut buf, &self.data, &self.key)
+                .unwrap();
+            assert_eq!(buf, self.sha256, "Vector: \"{}\"", self.id);
+        }
+        if self.sha512.len() == 64 {
+            let mut buf = vec![0; 64];
+            HmacSha512::message_auth_code()
+                .auth(&mut buf, &self.data, &self.key)
+                .unwrap();
+            assert_eq!(buf, self.sha512, "Vector: \"{}\"", self.id);
+        }
+
+        self
+    }
+
+    // test incremental mac calculation
+    pub fn test_state(&self) -> &Self {
+        for &chunk in CHUNKS.iter() {
+            let mut state = HmacSha256::mac_state(&self.key).unwrap();
+            self.data.chunks(chunk).for_each(|c| state.update(c).unwrap());
+            let mut buf = vec![0; 32];
+            state.finalize(&mut buf).unwrap();
+            assert_eq!(buf[..self.sha256.len()], self.sha256[..], "Vector: \"{}\"", self.id);
+
+            let mut state = HmacSha512::mac_state(&self.key).unwrap();
+            self.data.chunks(chunk).for_each(|c| state.update(c).unwrap());
+            let mut buf = vec![0; 64];
+            state.finalize(&mut buf).unwrap();
+            assert_eq!(buf[..self.sha512.len()], self.sha512[..], "Vector: \"{}\"", self.id);
+        }
+
+        self
+    }
+
+    // test tag verification
+    pub fn test_verify(&self) -> &Self {
+        HmacSha256.verify(&self.sha256, &self.data, &self.key).unwrap();
+        HmacSha512.verify(&self.sha512, &self.data, &self.key).unwrap();
+
+        self
+    }
+
+    // test tag verification failures
+    pub fn test_error(&self) -> &Self {
+        let error = HmacSha256
+            .verify(&self.sha256, &self.data, &self.key)
+            .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), "Invalid Data", "Vector: \"{}\"", self.id);
+
+        let error = HmacSha512
+            .verify(&self.sha512, &self.data, &self.key)
+            .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), "Invalid Data", "Vector: \"{}\"", self.id);
+
+        self
+    }
+}
+
+#[test]
+fn test_crypto() {
+    for vec in TestVector::load("crypto") {
+        vec.test_mac().test_state().test_verify();
+    }
+}
+
+#[test]
+fn test_error() {
+    for vec in TestVector::load("error") {
+        vec.test_error();
+    }
+}
+
+// API test vector
+#[derive(Default, Clone, Debug)]
+pub struct ApiTestVector {
+    id: String,
+    truncated: bool,
+    state: bool,
+    key_len: usize,
+    data_len: usize,
+    buf_len_256: usize,
+    buf_len_512: usize,
+    error: String,
+}
+
+impl ApiTestVector {
+    // load json
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut defaults = Self::default();
+        defaults.load_json(&json["api"]["defaults"]);
+
+        let mut vecs = Vec::new();
+        for vec in json["api"]["tests"].members() {
+            let mut this = defaults.clone();
+            this.load_json(vec);
+            vecs.push(this);
+        }
+        vecs
+    }
+
+    // test the mac calculation
+    pub fn test_mac(&self) -> &Self {
+        let key = vec![0; self.key_len];
+        let data = vec![0; self.data_len];
+
+        let mut buf = vec![0; self.buf_len_256];
+        let error = match (self.truncated, self.state) {
+            (true, _) => HmacSha256::var_len_message_auth_code().varlen_auth(&mut buf, &data, &key),
+            (false, false) => HmacSha256::message_auth_code().auth(&mut buf, &data, &key),
+            (false, true) => HmacSha256::mac_state(&key)
+                .and_then(|mut state| state.update(&data).and_then(|_| state.finalize(&mut buf))),
+        }
+        .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), self.error, "Vector: \"{}\"", self.id);
+
+        let mut buf = vec![0; self.buf_len_512];
+        let error = match (self.truncated, self.state) {
+            (true, _) => HmacSha512::var_len_message_auth_code().varlen_auth(&mut buf, &data, &key),
+            (false, false) => HmacSha512::message_auth_code().auth(&mut buf, &data, &key),
+            (false, true) => HmacSha512::mac_state(&key)
+                .and_then(|mut state| state.update(&data).and_then(|_| state.finalize(&mut buf))),
+        }
+        .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), self.error, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // load json
+    fn load_json(&mut self, j: &JsonValue) {
+        self.id = j["id"].option_string(&self.id);
+        self.truncated = j["truncated"].as_bool().unwrap_or(self.truncated);
+        self.state = j["state"].as_bool().unwrap_or(self.state);
+        self.key_len = j["key_len"].option_usize(self.key_len);
+        self.data_len = j["data_len"].option_usize(self.data_len);
+        self.buf_len_256 = j["buf_len_256"].option_usize(self.buf_len_256);
+        self.buf_len_512 = j["buf_len_512"].option_usize(self.buf_len_512);
+        self.error = j["error"].option_string(&self.error);
+    }
+}
+#[test]
+fn test_api() {
+    for vec in ApiTestVector::load() {
+        vec.test_mac();
+    }
+}
@@ -17,6 +17,8 @@ use json::JsonValue;
 
 // vector data.
 const VECTORS: &str = include_str!("poly.json");
+// chunk sizes for the incremental MACs
+const CHUNKS: [usize; 4] = [1, 13, 16, 33];
 
 // struct for vector data
 #[derive(Debug)]
@@ -53,19 +55,43 @@ impl TestVector {
 
         self
     }
+
+    // test incremental mac calculation
+    pub fn test_state(&self) -> &Self {
+        for &chunk in CHUNKS.iter() {
+            let mut state = Poly1305::mac_state(&self.key).unwrap();
+            self.data.chunks(chunk).for_each(|c| state.update(c).unwrap());
+            let mut buf = vec![0; self.mac.len()];
+            state.finalize(&mut buf).unwrap();
+            assert_eq!(buf, self.mac, "Vector: \"{}\"", self.id);
+        }
+
+        self
+    }
 }
 
 #[test]
 fn test_crypto() {
     for vec in TestVector::load() {
-        vec.test_mac();
+        vec.test_mac().test_state();
     }
 }
 
+#[test]
+fn test_finalized() {
+    let mut state = Poly1305::mac_state(&[0; 32]).unwrap();
+    state.finalize(&mut [0; 16]).unwrap();
+    let error = state.update(&[0; 64]).error_or("Update after finalize");
+    assert_eq!(error.to_string(), "Error: `State already finalized`");
+    let error = state.finalize(&mut [0; 16]).error_or("Finalize after finalize");
+    assert_eq!(error.to_string(), "Error: `State already finalized`");
+}
+
 // API test vector
 #[derive(Default, Clone, Debug)]
 pub struct ApiTestVector {
     id: String,
+    state: bool,
     key_len: usize,
     data_len: usize,
     buf_len: usize,
@@ -94,9 +120,12 @@ impl ApiTestVector {
         let data = vec![0; self.data_len];
         let mut buf = vec![0; self.buf_len];
 
-        let error = Poly1305::message_auth_code()
-            .auth(&mut buf, &data, &key)
-            .error_or(format!("Vector: \"{}\"", self.id));
+        let result = match self.state {
+            true => Poly1305::mac_state(&key)
+                .and_then(|mut state| state.update(&data).and_then(|_| state.finalize(&mut buf))),
+            false => Poly1305::message_auth_code().auth(&mut buf, &data, &key),
+        };
+        let error = result.error_or(format!("Vector: \"{}\"", self.id));
         assert_eq!(error.to_string(), self.error, "Vector: \"{}\"", self.id);
 
         self
@@ -105,6 +134,7 @@ impl ApiTestVector {
     // load json
     fn load_json(&mut self, j: &JsonValue) {
         self.id = j["id"].option_string(&self.id);
+        self.state = j["state"].as_bool().unwrap_or(self.state);
         self.key_len = j["key_len"].option_usize(self.key_len);
         self.data_len = j["data_len"].option_usize(self.data_len);
         self.buf_len = j["buf_len"].option_usize(self.buf_len);
@@ -0,0 +1,141 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod common;
+
+use common::{JsonValueExt, ResultExt};
+use crypto::Registry;
+
+// vector data.
+const VECTORS: &str = include_str!("registry.json");
+
+// struct for vector data
+#[derive(Debug)]
+struct TestVector {
+    kind: String,
+    ids: Vec<String>,
+}
+
+impl TestVector {
+    // load json vectors
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut vecs = Vec::new();
+        for vec in json["crypto"].check_array_iter() {
+            vecs.push(Self {
+                kind: vec["kind"].check_string(),
+                ids: vec["ids"].check_array_iter().map(|id| id.check_string()).collect(),
+            });
+        }
+        vecs
+    }
+
+    // test the registered ids and the lookup by id
+    pub fn test_lookup(&self) -> &Self {
+        let registered = match self.kind.as_str() {
+            "cipher" => Registry::cipher_ids(),
+            "aead_cipher" => Registry::aead_cipher_ids(),
+            "message_auth_code" => Registry::message_auth_code_ids(),
+            "hasher" => Registry::hasher_ids(),
+            _ => panic!("Vector: \"{}\"", self.kind),
+        };
+        assert_eq!(registered, self.ids, "Vector: \"{}\"", self.kind);
+
+        for id in self.ids.iter() {
+            let info_id = match self.kind.as_str() {
+                "cipher" => Registry::cipher(id).unwrap().info().id,
+                "aead_cipher" => Registry::aead_cipher(id).unwrap().info().id,
+                "message_auth_code" => Registry::message_auth_code(id).unwrap().info().id,
+                "hasher" => Registry::hasher(id).unwrap().info().id,
+                _ => panic!("Vector: \"{}\"", self.kind),
+            };
+            assert_eq!(info_id, id, "Vector: \"{}\"", self.kind);
+        }
+
+        self
+    }
+}
+
+#[test]
+fn test_crypto() {
+    for vec in TestVector::load() {
+        vec.test_lookup();
+    }
+}
+
+// unknown id vector
+#[derive(Debug)]
+struct ErrorTestVector {
+    id: String,
+    kind: String,
+    algorithm: String,
+}
+
+impl ErrorTestVector {
+    // load json
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut vecs = Vec::new();
+        for vec in json["error"].check_array_iter() {
+            vecs.push(Self {
+                id: vec["id"].check_string(),
+                kind: vec["kind"].check_string(),
+                algorithm: vec["algorithm"].check_string(),
+            });
+        }
+        vecs
+    }
+
+    // test the lookup of unknown ids
+    pub fn test_lookup(&self) -> &Self {
+        let error = match self.kind.as_str() {
+            "cipher" => Registry::cipher(&self.algorithm).map(|_| ()),
+            "aead_cipher" => Registry::aead_cipher(&self.algorithm).map(|_| ()),
+            "message_auth_code" => Registry::message_auth_code(&self.algorithm).map(|_| ()),
+            "hasher" => Registry::hasher(&self.algorithm).map(|_| ()),
+            _ => panic!("Vector: \"{}\"", self.id),
+        }
+        .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(
+            error.to_string(),
+            "Error: `Unknown algorithm id`",
+            "Vector: \"{}\"",
+            self.id
+        );
+
+        self
+    }
+}
+
+#[test]
+fn test_error() {
+    for vec in ErrorTestVector::load() {
+        vec.test_lookup();
+    }
+}
+
+#[test]
+fn test_roundtrip() {
+    // data sealed with a registered AEAD Cipher can be opened with the Cipher looked up by the recorded id
+    for id in Registry::aead_cipher_ids() {
+        let sealer = Registry::aead_cipher(id).unwrap();
+        let (key, nonce) = ([7; 32], vec![9; sealer.info().nonce_lens.start]);
+        let mut buf = vec![0; 64 + 16];
+        let len = sealer.seal_with(&mut buf, &[42; 64], b"ad", &key, &nonce).unwrap();
+
+        let mut plain = vec![0; 64];
+        Registry::aead_cipher(sealer.info().id)
+            .unwrap()
+            .open_to(&mut plain, &buf[..len], b"ad", &key, &nonce)
+            .unwrap();
+        assert_eq!(plain, vec![42; 64], "Vector: \"{}\"", id);
+    }
+}
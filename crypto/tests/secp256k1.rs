@@ -0,0 +1,241 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod common;
+
+use common::{JsonValueExt, ResultExt};
+use crypto::{primitives::rng::PublicKeyGen, Secp256k1};
+use json::JsonValue;
+use std::convert::TryInto;
+
+// vector data.
+const VECTORS: &str = include_str!("secp256k1.json");
+
+// struct for vector data
+#[derive(Debug)]
+struct TestVector {
+    id: String,
+    secret_key: Vec<u8>,
+    public_key: Vec<u8>,
+    public_key_uncompressed: Vec<u8>,
+    data: Vec<u8>,
+    hash: Vec<u8>,
+    signature: Vec<u8>,
+    recovery_id: u8,
+}
+
+impl TestVector {
+    // load json vectors
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut vecs = Vec::new();
+        for vec in json["crypto"].check_array_iter() {
+            vecs.push(Self {
+                id: vec["id"].check_string(),
+                secret_key: vec["secret_key"].check_bytes(),
+                public_key: vec["public_key"].check_bytes(),
+                public_key_uncompressed: vec["public_key_uncompressed"].check_bytes(),
+                data: vec["data"].check_bytes(),
+                hash: vec["hash"].check_bytes(),
+                signature: vec["signature"].check_bytes(),
+                recovery_id: vec["recovery_id"].as_u8().unwrap(),
+            });
+        }
+        vecs
+    }
+
+    // test the public key derivation
+    pub fn test_public_key(&self) -> &Self {
+        let mut buf = vec![0; self.public_key.len()];
+        Secp256k1.get_pub_key(&mut buf, &self.secret_key).unwrap();
+        assert_eq!(buf, self.public_key, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // test signing
+    pub fn test_sign(&self) -> &Self {
+        let mut buf = vec![0; self.signature.len()];
+        Secp256k1::signer()
+            .sign(&mut buf, &self.data, &self.secret_key)
+            .unwrap();
+        assert_eq!(buf, self.signature, "Vector: \"{}\"", self.id);
+
+        let mut buf = vec![0; self.signature.len()];
+        Secp256k1
+            .sign_prehashed(&mut buf, self.hash(), &self.secret_key)
+            .unwrap();
+        assert_eq!(buf, self.signature, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // test verification with compressed and uncompressed public keys
+    pub fn test_verify(&self) -> &Self {
+        Secp256k1::signer()
+            .verify(&self.data, &self.signature, &self.public_key)
+            .unwrap();
+        Secp256k1::signer()
+            .verify(&self.data, &self.signature, &self.public_key_uncompressed)
+            .unwrap();
+        Secp256k1
+            .verify_prehashed(self.hash(), &self.signature, &self.public_key)
+            .unwrap();
+
+        self
+    }
+
+    // test recoverable signatures
+    pub fn test_recover(&self) -> &Self {
+        let mut sig = vec![0; self.signature.len() + 1];
+        Secp256k1
+            .sign_recoverable(&mut sig, self.hash(), &self.secret_key)
+            .unwrap();
+        assert_eq!(sig[..64], self.signature[..], "Vector: \"{}\"", self.id);
+        assert_eq!(sig[64], self.recovery_id, "Vector: \"{}\"", self.id);
+
+        let mut buf = vec![0; self.public_key.len()];
+        Secp256k1.recover(&mut buf, self.hash(), &sig).unwrap();
+        assert_eq!(buf, self.public_key, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // the hash as fixed size array
+    fn hash(&self) -> &[u8; 32] {
+        self.hash[..].try_into().unwrap()
+    }
+}
+
+#[test]
+fn test_crypto() {
+    for vec in TestVector::load() {
+        vec.test_public_key().test_sign().test_verify().test_recover();
+    }
+}
+
+// struct for error vector data
+#[derive(Debug)]
+struct ErrorTestVector {
+    id: String,
+    public_key: Vec<u8>,
+    data: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+impl ErrorTestVector {
+    // load json vectors
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut vecs = Vec::new();
+        for vec in json["error"].check_array_iter() {
+            vecs.push(Self {
+                id: vec["id"].check_string(),
+                public_key: vec["public_key"].check_bytes(),
+                data: vec["data"].check_bytes(),
+                signature: vec["signature"].check_bytes(),
+            });
+        }
+        vecs
+    }
+
+    // test the rejection of invalid signatures
+    pub fn test_verify(&self) -> &Self {
+        let error = Secp256k1::signer()
+            .verify(&self.data, &self.signature, &self.public_key)
+            .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), "Invalid Data", "Vector: \"{}\"", self.id);
+
+        self
+    }
+}
+
+#[test]
+fn test_error() {
+    for vec in ErrorTestVector::load() {
+        vec.test_verify();
+    }
+}
+
+// API test vector
+#[derive(Default, Clone, Debug)]
+pub struct ApiTestVector {
+    id: String,
+    mode: String,
+    secret_key: Option<Vec<u8>>,
+    secret_key_len: usize,
+    public_key_len: usize,
+    buf_len: usize,
+    sig_len: usize,
+    error: String,
+}
+
+impl ApiTestVector {
+    // load json
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut defaults = Self::default();
+        defaults.load_json(&json["api"]["defaults"]);
+
+        let mut vecs = Vec::new();
+        for vec in json["api"]["tests"].members() {
+            let mut this = defaults.clone();
+            this.load_json(vec);
+            vecs.push(this);
+        }
+        vecs
+    }
+
+    // test the parameter validation
+    pub fn test_api(&self) -> &Self {
+        let secret_key = match &self.secret_key {
+            Some(secret_key) => secret_key.clone(),
+            None => vec![0; self.secret_key_len],
+        };
+        let public_key = vec![0; self.public_key_len];
+        let sig = vec![0; self.sig_len];
+        let mut buf = vec![0; self.buf_len];
+        let hash = [0; 32];
+
+        let error = match self.mode.as_str() {
+            "sign" => Secp256k1::signer().sign(&mut buf, &[], &secret_key).map(|_| ()),
+            "sign_recoverable" => Secp256k1.sign_recoverable(&mut buf, &hash, &secret_key).map(|_| ()),
+            "public_key" => Secp256k1.get_pub_key(&mut buf, &secret_key).map(|_| ()),
+            "verify" => Secp256k1::signer().verify(&[], &sig, &public_key),
+            "recover" => Secp256k1.recover(&mut buf, &hash, &sig).map(|_| ()),
+            _ => panic!("Vector: \"{}\"", self.id),
+        }
+        .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), self.error, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // load json
+    fn load_json(&mut self, j: &JsonValue) {
+        self.id = j["id"].option_string(&self.id);
+        self.mode = j["mode"].option_string(&self.mode);
+        if !j["secret_key"].is_null() {
+            self.secret_key = Some(j["secret_key"].check_bytes());
+        }
+        self.secret_key_len = j["secret_key_len"].option_usize(self.secret_key_len);
+        self.public_key_len = j["public_key_len"].option_usize(self.public_key_len);
+        self.buf_len = j["buf_len"].option_usize(self.buf_len);
+        self.sig_len = j["sig_len"].option_usize(self.sig_len);
+        self.error = j["error"].option_string(&self.error);
+    }
+}
+#[test]
+fn test_api() {
+    for vec in ApiTestVector::load() {
+        vec.test_api();
+    }
+}
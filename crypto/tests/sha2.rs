@@ -0,0 +1,189 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod common;
+
+use common::{JsonValueExt, ResultExt};
+use crypto::{primitives::hash::HashInfo, Sha256, Sha512};
+use json::JsonValue;
+
+// vector data.
+const VECTORS: &str = include_str!("sha2.json");
+// chunk sizes for the incremental hashes
+const CHUNKS: [usize; 4] = [1, 13, 64, 129];
+
+// struct for vector data
+#[derive(Debug)]
+struct TestVector {
+    id: String,
+    data: Vec<u8>,
+    sha256: Vec<u8>,
+    sha512: Vec<u8>,
+}
+
+impl TestVector {
+    // load json vectors
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut vecs = Vec::new();
+        for vec in json["crypto"].check_array_iter() {
+            vecs.push(Self {
+                id: vec["id"].check_string(),
+                data: vec["data"].check_bytes().repeat(vec["repeat"].option_usize(1)),
+                sha256: vec["sha256"].check_bytes(),
+                sha512: vec["sha512"].check_bytes(),
+            });
+        }
+        vecs
+    }
+
+    // test SHA-256 calculation
+    pub fn test_sha256(&self) -> &Self {
+        let mut buf = vec![0; self.sha256.len()];
+        let len = Sha256::hasher().hash(&mut buf, &self.data).unwrap();
+        assert_eq!(len, self.sha256.len(), "Vector: \"{}\"", self.id);
+        assert_eq!(buf, self.sha256, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // test SHA-512 calculation
+    pub fn test_sha512(&self) -> &Self {
+        let mut buf = vec![0; self.sha512.len()];
+        let len = Sha512::hasher().hash(&mut buf, &self.data).unwrap();
+        assert_eq!(len, self.sha512.len(), "Vector: \"{}\"", self.id);
+        assert_eq!(buf, self.sha512, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // test incremental SHA-256 and SHA-512 calculation
+    pub fn test_state(&self) -> &Self {
+        for &chunk in CHUNKS.iter() {
+            let mut state = Sha256::hash_state();
+            self.data.chunks(chunk).for_each(|c| state.update(c).unwrap());
+            let mut buf = vec![0; self.sha256.len()];
+            let len = state.finalize(&mut buf).unwrap();
+            assert_eq!(len, self.sha256.len(), "Vector: \"{}\"", self.id);
+            assert_eq!(buf, self.sha256, "Vector: \"{}\"", self.id);
+
+            let mut state = Sha512::hash_state();
+            self.data.chunks(chunk).for_each(|c| state.update(c).unwrap());
+            let mut buf = vec![0; self.sha512.len()];
+            let len = state.finalize(&mut buf).unwrap();
+            assert_eq!(len, self.sha512.len(), "Vector: \"{}\"", self.id);
+            assert_eq!(buf, self.sha512, "Vector: \"{}\"", self.id);
+        }
+
+        self
+    }
+}
+
+#[test]
+fn test_crypto() {
+    for vec in TestVector::load() {
+        vec.test_sha256().test_sha512().test_state();
+    }
+}
+
+#[test]
+fn test_info() {
+    assert_eq!(
+        Sha256::hasher().info(),
+        HashInfo {
+            id: "Sha256",
+            hash_len: 32,
+            hash_lens: 32..32
+        }
+    );
+    assert_eq!(
+        Sha512::hasher().info(),
+        HashInfo {
+            id: "Sha512",
+            hash_len: 64,
+            hash_lens: 64..64
+        }
+    );
+    assert_eq!(Sha256::hash_state().info(), Sha256::hasher().info());
+    assert_eq!(Sha512::hash_state().info(), Sha512::hasher().info());
+}
+
+#[test]
+fn test_finalized() {
+    let mut state = Sha256::hash_state();
+    state.finalize(&mut [0; 32]).unwrap();
+    let error = state.update(&[0; 64]).error_or("Update after finalize");
+    assert_eq!(error.to_string(), "Error: `State already finalized`");
+    let error = state.finalize(&mut [0; 32]).error_or("Finalize after finalize");
+    assert_eq!(error.to_string(), "Error: `State already finalized`");
+}
+
+// API test vector
+#[derive(Default, Clone, Debug)]
+pub struct ApiTestVector {
+    id: String,
+    hash: String,
+    state: bool,
+    data_len: usize,
+    buf_len: usize,
+    error: String,
+}
+
+impl ApiTestVector {
+    // load json
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut defaults = Self::default();
+        defaults.load_json(&json["api"]["defaults"]);
+
+        let mut vecs = Vec::new();
+        for vec in json["api"]["tests"].members() {
+            let mut this = defaults.clone();
+            this.load_json(vec);
+            vecs.push(this);
+        }
+        vecs
+    }
+
+    // test the hash calculation
+    pub fn test_hash(&self) -> &Self {
+        let data = vec![0; self.data_len];
+        let mut buf = vec![0; self.buf_len];
+
+        let result = match (self.hash.as_str(), self.state) {
+            ("sha256", false) => Sha256::hasher().hash(&mut buf, &data),
+            ("sha512", false) => Sha512::hasher().hash(&mut buf, &data),
+            ("sha256", true) => Sha256::hash_state().finalize(&mut buf),
+            ("sha512", true) => Sha512::hash_state().finalize(&mut buf),
+            _ => panic!("Vector: \"{}\"", self.id),
+        };
+        let error = result.error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), self.error, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // load json
+    fn load_json(&mut self, j: &JsonValue) {
+        self.id = j["id"].option_string(&self.id);
+        self.hash = j["hash"].option_string(&self.hash);
+        self.state = j["state"].as_bool().unwrap_or(self.state);
+        self.data_len = j["data_len"].option_usize(self.data_len);
+        self.buf_len = j["buf_len"].option_usize(self.buf_len);
+        self.error = j["error"].option_string(&self.error);
+    }
+}
+#[test]
+fn test_api() {
+    for vec in ApiTestVector::load() {
+        vec.test_hash();
+    }
+}
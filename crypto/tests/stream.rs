@@ -0,0 +1,254 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod common;
+
+use common::{JsonValueExt, ResultExt};
+use crypto::{
+    primitives::{rng::SecureRng, Error},
+    Sha256, StreamDecryptor, StreamEncryptor,
+};
+use json::JsonValue;
+use std::io::{Read, Write};
+
+// vector data.
+const VECTORS: &str = include_str!("stream.json");
+// size of a plaintext chunk
+const CHUNK: usize = 65536;
+// size of a sealed chunk
+const SEALED_CHUNK: usize = CHUNK + 16;
+// size of the stream header
+const HEADER: usize = 19;
+// write sizes for the encryptor
+const WRITES: [usize; 3] = [1000, 65536, 100_000];
+
+// rng that returns a fixed header
+struct FixedRng(Vec<u8>);
+impl SecureRng for FixedRng {
+    fn random(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        buf.copy_from_slice(&self.0[..buf.len()]);
+        Ok(())
+    }
+}
+
+// encrypts the data in writes of `write` bytes
+fn encrypt(data: &[u8], key: &[u8], header: &[u8], write: usize) -> Vec<u8> {
+    let mut encryptor = StreamEncryptor::new(Vec::new(), key, &mut FixedRng(header.to_vec())).unwrap();
+    data.chunks(write).for_each(|c| encryptor.write_all(c).unwrap());
+    encryptor.finish().unwrap()
+}
+
+// struct for vector data
+#[derive(Debug)]
+struct TestVector {
+    id: String,
+    key: Vec<u8>,
+    header: Vec<u8>,
+    data: Vec<u8>,
+    cipher: Option<Vec<u8>>,
+    cipher_sha256: Vec<u8>,
+}
+
+impl TestVector {
+    // load json vectors
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut vecs = Vec::new();
+        for vec in json["crypto"].check_array_iter() {
+            vecs.push(Self {
+                id: vec["id"].check_string(),
+                key: vec["key"].check_bytes(),
+                header: vec["header"].check_bytes(),
+                data: vec["data"].check_bytes().repeat(vec["repeat"].option_usize(1)),
+                cipher: match vec["cipher"].is_string() {
+                    true => Some(vec["cipher"].check_bytes()),
+                    false => None,
+                },
+                cipher_sha256: vec["cipher_sha256"].check_bytes(),
+            });
+        }
+        vecs
+    }
+
+    // test the stream encryption
+    pub fn test_encryption(&self) -> &Self {
+        for &write in WRITES.iter() {
+            let cipher = encrypt(&self.data, &self.key, &self.header, write);
+            if let Some(expected) = &self.cipher {
+                assert_eq!(&cipher, expected, "Vector: \"{}\"", self.id);
+            }
+
+            let mut hash = [0; 32];
+            Sha256::hasher().hash(&mut hash, &cipher).unwrap();
+            assert_eq!(hash.to_vec(), self.cipher_sha256, "Vector: \"{}\"", self.id);
+        }
+
+        self
+    }
+
+    // test the stream decryption
+    pub fn test_decryption(&self) -> &Self {
+        let cipher = encrypt(&self.data, &self.key, &self.header, CHUNK);
+
+        let mut decryptor = StreamDecryptor::new(cipher.as_slice(), &self.key).unwrap();
+        let mut plain = Vec::new();
+        decryptor.read_to_end(&mut plain).unwrap();
+        assert_eq!(plain, self.data, "Vector: \"{}\"", self.id);
+
+        // small reads have to return the same data
+        let mut decryptor = StreamDecryptor::new(cipher.as_slice(), &self.key).unwrap();
+        let (mut plain, mut buf) = (Vec::new(), [0; 1000]);
+        loop {
+            match decryptor.read(&mut buf).unwrap() {
+                0 => break,
+                len => plain.extend_from_slice(&buf[..len]),
+            }
+        }
+        assert_eq!(plain, self.data, "Vector: \"{}\"", self.id);
+
+        self
+    }
+}
+
+#[test]
+fn test_crypto() {
+    for vec in TestVector::load() {
+        vec.test_encryption().test_decryption();
+    }
+}
+
+// struct for error vector data
+#[derive(Debug)]
+struct ErrorTestVector {
+    id: String,
+    data_len: usize,
+    mutation: String,
+    offset: usize,
+    start: usize,
+    end: usize,
+}
+
+impl ErrorTestVector {
+    // load json vectors
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut vecs = Vec::new();
+        for vec in json["error"].check_array_iter() {
+            vecs.push(Self {
+                id: vec["id"].check_string(),
+                data_len: vec["data_len"].option_usize(0),
+                mutation: vec["mutation"].check_string(),
+                offset: vec["offset"].option_usize(0),
+                start: vec["start"].option_usize(0),
+                end: vec["end"].option_usize(0),
+            });
+        }
+        vecs
+    }
+
+    // test that a modified stream fails to decrypt
+    pub fn test_error(&self) -> &Self {
+        let (key, data) = ([0; 32], vec![0; self.data_len]);
+        let mut cipher = encrypt(&data, &key, &[0; HEADER], CHUNK);
+
+        match self.mutation.as_str() {
+            "flip" => cipher[self.offset] ^= 0x01,
+            "cut" => {
+                cipher.drain(self.start..self.end);
+            }
+            "swap" => {
+                let (first, second) = cipher[HEADER..HEADER + 2 * SEALED_CHUNK].split_at_mut(SEALED_CHUNK);
+                first.swap_with_slice(second);
+            }
+            "append" => cipher.push(0),
+            _ => panic!("Vector: \"{}\"", self.id),
+        }
+
+        let mut decryptor = StreamDecryptor::new(cipher.as_slice(), &key).unwrap();
+        let error = decryptor
+            .read_to_end(&mut Vec::new())
+            .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), "Invalid Data", "Vector: \"{}\"", self.id);
+
+        // the decryptor refuses to continue after an error
+        let error = decryptor
+            .read(&mut [0; 16])
+            .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), "Invalid Data", "Vector: \"{}\"", self.id);
+
+        self
+    }
+}
+
+#[test]
+fn test_error() {
+    for vec in ErrorTestVector::load() {
+        vec.test_error();
+    }
+}
+
+// API test vector
+#[derive(Default, Clone, Debug)]
+pub struct ApiTestVector {
+    id: String,
+    mode: String,
+    key_len: usize,
+    header_len: usize,
+    error: String,
+}
+
+impl ApiTestVector {
+    // load json
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut defaults = Self::default();
+        defaults.load_json(&json["api"]["defaults"]);
+
+        let mut vecs = Vec::new();
+        for vec in json["api"]["tests"].members() {
+            let mut this = defaults.clone();
+            this.load_json(vec);
+            vecs.push(this);
+        }
+        vecs
+    }
+
+    // test the parameter validation
+    pub fn test_api(&self) -> &Self {
+        let key = vec![0; self.key_len];
+        let header = vec![0; self.header_len];
+
+        let error = match self.mode.as_str() {
+            "encrypt" => StreamEncryptor::new(Vec::new(), &key, &mut FixedRng(header)).map(|_| ()),
+            "decrypt" => StreamDecryptor::new(header.as_slice(), &key).map(|_| ()),
+            _ => panic!("Vector: \"{}\"", self.id),
+        }
+        .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), self.error, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // load json
+    fn load_json(&mut self, j: &JsonValue) {
+        self.id = j["id"].option_string(&self.id);
+        self.mode = j["mode"].option_string(&self.mode);
+        self.key_len = j["key_len"].option_usize(self.key_len);
+        self.header_len = j["header_len"].option_usize(self.header_len);
+        self.error = j["error"].option_string(&self.error);
+    }
+}
+#[test]
+fn test_api() {
+    for vec in ApiTestVector::load() {
+        vec.test_api();
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod common;
+
+use common::{JsonValueExt, ResultExt};
+use crypto::{primitives::rng::PublicKeyGen, X25519};
+use json::JsonValue;
+
+// vector data.
+const VECTORS: &str = include_str!("x25519.json");
+
+// struct for vector data
+#[derive(Debug)]
+struct TestVector {
+    id: String,
+    secret_key: Vec<u8>,
+    public_key: Vec<u8>,
+    shared_secret: Vec<u8>,
+}
+
+impl TestVector {
+    // load json vectors
+    pub fn load(section: &str) -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut vecs = Vec::new();
+        for vec in json[section].check_array_iter() {
+            vecs.push(Self {
+                id: vec["id"].check_string(),
+                secret_key: vec["secret_key"].check_bytes(),
+                public_key: vec["public_key"].check_bytes(),
+                shared_secret: match vec["shared_secret"].is_null() {
+                    true => Vec::new(),
+                    false => vec["shared_secret"].check_bytes(),
+                },
+            });
+        }
+        vecs
+    }
+
+    // test the key exchange
+    pub fn test_exchange(&self) -> &Self {
+        let mut buf = vec![0; self.shared_secret.len()];
+        X25519::key_exchange()
+            .exchange(&mut buf, &self.secret_key, &self.public_key)
+            .unwrap();
+        assert_eq!(buf, self.shared_secret, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // test the public key derivation
+    pub fn test_public_key(&self) -> &Self {
+        let mut buf = vec![0; self.public_key.len()];
+        X25519.get_pub_key(&mut buf, &self.secret_key).unwrap();
+        assert_eq!(buf, self.public_key, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // test the rejection of small order public keys
+    pub fn test_error(&self) -> &Self {
+        let mut buf = vec![0; 32];
+        let error = X25519::key_exchange()
+            .exchange(&mut buf, &self.secret_key, &self.public_key)
+            .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), "Error: `Invalid public key`", "Vector: \"{}\"", self.id);
+
+        self
+    }
+}
+
+#[test]
+fn test_crypto() {
+    for vec in TestVector::load("crypto") {
+        vec.test_exchange();
+    }
+}
+
+#[test]
+fn test_public_key() {
+    for vec in TestVector::load("keys") {
+        vec.test_public_key();
+    }
+}
+
+#[test]
+fn test_error() {
+    for vec in TestVector::load("error") {
+        vec.test_error();
+    }
+}
+
+#[test]
+fn test_iterations() {
+    let json = json::parse(VECTORS).unwrap();
+    for vec in json["iterations"].check_array_iter() {
+        let (id, result) = (vec["id"].check_string(), vec["result"].check_bytes());
+
+        // k = u = 9, then repeatedly k, u = X25519(k, u), k
+        let (mut k, mut u) = ([0; 32], [0; 32]);
+        k[0] = 9;
+        u[0] = 9;
+        for _ in 0..vec["iterations"].as_usize().unwrap() {
+            let mut r = [0; 32];
+            X25519::key_exchange().exchange(&mut r, &k, &u).unwrap();
+            u = k;
+            k = r;
+        }
+        assert_eq!(k[..], result[..], "Vector: \"{}\"", id);
+    }
+}
+
+// API test vector
+#[derive(Default, Clone, Debug)]
+pub struct ApiTestVector {
+    id: String,
+    mode: String,
+    secret_key_len: usize,
+    public_key_len: usize,
+    buf_len: usize,
+    error: String,
+}
+
+impl ApiTestVector {
+    // load json
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut defaults = Self::default();
+        defaults.load_json(&json["api"]["defaults"]);
+
+        let mut vecs = Vec::new();
+        for vec in json["api"]["tests"].members() {
+            let mut this = defaults.clone();
+            this.load_json(vec);
+            vecs.push(this);
+        }
+        vecs
+    }
+
+    // test the parameter validation
+    pub fn test_api(&self) -> &Self {
+        let secret_key = vec![0; self.secret_key_len];
+        let mut public_key = vec![0; self.public_key_len];
+        public_key[0] = 9;
+        let mut buf = vec![0; self.buf_len];
+
+        let error = match self.mode.as_str() {
+            "exchange" => X25519::key_exchange().exchange(&mut buf, &secret_key, &public_key),
+            "public_key" => X25519.get_pub_key(&mut buf, &secret_key),
+            _ => panic!("Vector: \"{}\"", self.id),
+        }
+        .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), self.error, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // load json
+    fn load_json(&mut self, j: &JsonValue) {
+        self.id = j["id"].option_string(&self.id);
+        self.mode = j["mode"].option_string(&self.mode);
+        self.secret_key_len = j["secret_key_len"].option_usize(self.secret_key_len);
+        self.public_key_len = j["public_key_len"].option_usize(self.public_key_len);
+        self.buf_len = j["buf_len"].option_usize(self.buf_len);
+        self.error = j["error"].option_string(&self.error);
+    }
+}
+#[test]
+fn test_api() {
+    for vec in ApiTestVector::load() {
+        vec.test_api();
+    }
+}
@@ -83,12 +83,32 @@ impl TestVector {
 
         self
     }
+
+    // test detached encryption and decryption
+    pub fn test_detached(&self) -> &Self {
+        let (cipher, tag) = self.cipher.split_at(self.plain.len());
+
+        let mut buf = self.plain.clone();
+        let mut buf_tag = vec![0; 16];
+        let tag_len = XChaChaPoly::detached_aead_cipher()
+            .seal_detached(&mut buf, &mut buf_tag, &self.ad, &self.key, &self.nonce)
+            .unwrap();
+        assert_eq!(buf, cipher, "Vector: \"{}\"", self.id);
+        assert_eq!(&buf_tag[..tag_len], tag, "Vector: \"{}\"", self.id);
+
+        let len = XChaChaPoly::detached_aead_cipher()
+            .open_detached(&mut buf, tag, &self.ad, &self.key, &self.nonce)
+            .unwrap();
+        assert_eq!(&buf[..len], self.plain.as_slice(), "Vector: \"{}\"", self.id);
+
+        self
+    }
 }
 
 #[test]
 fn test_crypto() {
     for vec in TestVector::load() {
-        vec.test_encryption().test_decryption();
+        vec.test_encryption().test_decryption().test_detached();
     }
 }
 
@@ -133,6 +153,15 @@ impl ErrorTestVector {
             .error_or(format!("Vector: \"{}\"", self.id));
         assert_eq!(error.to_string(), "Invalid Data", "Vector: \"{}\"", self.id);
 
+        // detached decryption must leave the data untouched
+        let (cipher, tag) = self.cipher.split_at(self.cipher.len().saturating_sub(16));
+        let mut buf = cipher.to_vec();
+        let error = XChaChaPoly::detached_aead_cipher()
+            .open_detached(&mut buf, tag, &self.ad, &self.key, &self.nonce)
+            .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), "Invalid Data", "Vector: \"{}\"", self.id);
+        assert_eq!(buf, cipher, "Vector: \"{}\"", self.id);
+
         self
     }
 }
@@ -235,3 +264,71 @@ fn test_api() {
         vec.test_encryption().test_decryption();
     }
 }
+
+// detached API test vector
+#[derive(Default, Clone, Debug)]
+pub struct DetachedApiTestVector {
+    id: String,
+    mode: String,
+    key_len: usize,
+    nonce_len: usize,
+    ad_len: usize,
+    data_len: usize,
+    tag_len: usize,
+    error: String,
+}
+impl DetachedApiTestVector {
+    // load json vectors
+    pub fn load() -> Vec<Self> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut defaults = Self::default();
+        defaults.load_json(&json["detached_api"]["defaults"]);
+
+        let mut vecs = Vec::new();
+        for vec in json["detached_api"]["tests"].members() {
+            let mut this = defaults.clone();
+            this.load_json(vec);
+            vecs.push(this);
+        }
+
+        vecs
+    }
+
+    // test the parameter validation
+    pub fn test_api(&self) -> &Self {
+        let key = vec![0; self.key_len];
+        let nonce = vec![0; self.nonce_len];
+        let ad = vec![0; self.ad_len];
+        let mut data = vec![0; self.data_len];
+        let mut tag = vec![0; self.tag_len];
+
+        let cipher = XChaChaPoly::detached_aead_cipher();
+        let error = match self.mode.as_str() {
+            "seal" => cipher.seal_detached(&mut data, &mut tag, &ad, &key, &nonce),
+            "open" => cipher.open_detached(&mut data, &tag, &ad, &key, &nonce),
+            _ => panic!("Vector: \"{}\"", self.id),
+        }
+        .error_or(format!("Vector: \"{}\"", self.id));
+        assert_eq!(error.to_string(), self.error, "Vector: \"{}\"", self.id);
+
+        self
+    }
+
+    // load json
+    fn load_json(&mut self, j: &JsonValue) {
+        self.id = j["id"].option_string(&self.id);
+        self.mode = j["mode"].option_string(&self.mode);
+        self.key_len = j["key_len"].option_usize(self.key_len);
+        self.nonce_len = j["nonce_len"].option_usize(self.nonce_len);
+        self.ad_len = j["ad_len"].option_usize(self.ad_len);
+        self.data_len = j["data_len"].option_usize(self.data_len);
+        self.tag_len = j["tag_len"].option_usize(self.tag_len);
+        self.error = j["error"].option_string(&self.error);
+    }
+}
+#[test]
+fn test_detached_api() {
+    for vec in DetachedApiTestVector::load() {
+        vec.test_api();
+    }
+}
@@ -9,9 +9,9 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-use crate::rng::SecretKeyGen;
+use crate::{rng::SecretKeyGen, Error};
 
-use std::{error::Error, ops::Range};
+use std::ops::Range;
 
 /// Message Authentication Code information block
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -33,11 +33,21 @@ pub trait MessageAuthCode: SecretKeyGen {
     /// get the info about the MAC
     fn info(&self) -> MessageAuthCodeInfo;
     /// authenticate the `data` using the `key` through the `buf` buffer.  Returns the MAC length in a `Result`
-    fn auth(&self, buf: &mut [u8], data: &[u8], key: &[u8]) -> Result<usize, Box<dyn Error + 'static>>;
+    fn auth(&self, buf: &mut [u8], data: &[u8], key: &[u8]) -> Result<usize, Error>;
 }
 
 /// an extension for a Variable length Message Authentication Code (MAC).
 pub trait VarLenMessageAuthCode: MessageAuthCode {
     /// Authenticates the `data` using a `key` through the `buf` buffer.  Returns the MAC's length in a `Result`.
-    fn varlen_auth(&self, buf: &mut [u8], data: &[u8], key: &[u8]) -> Result<usize, Box<dyn Error + 'static>>;
+    fn varlen_auth(&self, buf: &mut [u8], data: &[u8], key: &[u8]) -> Result<usize, Error>;
+}
+
+/// a stateful Message Authentication Code (MAC) that absorbs the data incrementally.
+pub trait MacState {
+    /// get the info about the MAC
+    fn info(&self) -> MessageAuthCodeInfo;
+    /// absorbs the next chunk of `data` into the state.
+    fn update(&mut self, data: &[u8]) -> Result<(), Error>;
+    /// writes the MAC into `buf` and returns the MAC length. The state can not be used after it was finalized.
+    fn finalize(&mut self, buf: &mut [u8]) -> Result<usize, Error>;
 }
@@ -9,9 +9,9 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-use std::{error::Error, ops::Range};
+use std::ops::Range;
 
-use crate::rng::SecretKeyGen;
+use crate::{rng::SecretKeyGen, Error};
 
 /// A block of information about the implemented Cipher
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -35,87 +35,52 @@ pub trait Cipher: SecretKeyGen {
     /// predicts the max encrypted cipher length given a `plaintext_len` (in bytes)
     fn predict_encrypted_max(&self, plain_len: usize) -> usize;
     /// encrypts the plaintext in-place and returns the cipher's length. `buf` contains the incoming plaintext buffer.
-    fn encrypt(
-        &self,
-        buf: &mut [u8],
-        plain_len: usize,
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>>;
+    fn encrypt(&self, buf: &mut [u8], plain_len: usize, key: &[u8], nonce: &[u8]) -> Result<usize, Error>;
 
     /// encrypts the plaintext and returns the plaintext's length. `buf` contains the incoming plaintext buffer.
-    fn encrypt_to(
-        &self,
-        buf: &mut [u8],
-        plain: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>>;
+    fn encrypt_to(&self, buf: &mut [u8], plain: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error>;
 
     /// decrypts the cipher's bytes in-place and returns the plaintext length. `buf` contains the outgoing plaintext
     /// buffer
-    fn decrypt(
-        &self,
-        buf: &mut [u8],
-        cipher_len: usize,
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>>;
+    fn decrypt(&self, buf: &mut [u8], cipher_len: usize, key: &[u8], nonce: &[u8]) -> Result<usize, Error>;
 
     /// decrypts the ciphertext and returns the plaintext's length. `buf` contains the outgoing plaintext
     /// buffer
-    fn decrypt_to(
-        &self,
-        buf: &mut [u8],
-        cipher: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>>;
+    fn decrypt_to(&self, buf: &mut [u8], cipher: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error>;
 }
 
 /// an AEAD Extension for the Cipher
 pub trait AeadCipher: Cipher {
     /// Seals the Plaintext bytes in place with AEAD and returns the Cipher length. `buf` contains the incoming
     /// plaintext buffer
-    fn seal(
-        &self,
-        buf: &mut [u8],
-        plain_len: usize,
-        ad: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>>;
+    fn seal(&self, buf: &mut [u8], plain_len: usize, ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error>;
 
     /// Seals the plaintext and returns the Cipher's length using AEAD. `buf` contains the incoming plaintext
     /// buffer
-    fn seal_with(
-        &self,
-        buf: &mut [u8],
-        plain: &[u8],
-        ad: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>>;
+    fn seal_with(&self, buf: &mut [u8], plain: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error>;
 
     /// Opens the Ciphertext length in-place and returns the plaintext length using AEAD. `buf` contains the outgoing
     /// plaintext buffer
-    fn open(
-        &self,
-        buf: &mut [u8],
-        cipher_len: usize,
-        ad: &[u8],
-        key: &[u8],
-        nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>>;
+    fn open(&self, buf: &mut [u8], cipher_len: usize, ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error>;
 
     /// Opens the Ciphertext and returns the plaintext length using AEAD. `buf` contains the outgoing
     /// plaintext buffer
-    fn open_to(
+    fn open_to(&self, buf: &mut [u8], cipher: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error>;
+}
+
+/// a detached tag extension for the AEAD Cipher
+pub trait DetachedAeadCipher: AeadCipher {
+    /// Seals the `data` in place with AEAD, writes the auth tag to `tag` and returns the tag's length.
+    fn seal_detached(
         &self,
-        buf: &mut [u8],
-        cipher: &[u8],
+        data: &mut [u8],
+        tag: &mut [u8],
         ad: &[u8],
         key: &[u8],
         nonce: &[u8],
-    ) -> Result<usize, Box<dyn Error + 'static>>;
+    ) -> Result<usize, Error>;
+
+    /// Opens the ciphertext `data` in place with the detached auth `tag` and returns the plaintext length using AEAD.
+    /// `data` is left untouched if the tag is invalid
+    fn open_detached(&self, data: &mut [u8], tag: &[u8], ad: &[u8], key: &[u8], nonce: &[u8]) -> Result<usize, Error>;
 }
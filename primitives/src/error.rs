@@ -0,0 +1,108 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The errors returned by the primitives. Invalid parameters have their own variants so that callers can tell a
+/// programming error apart from data that failed to authenticate or an RNG that failed.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Error {
+    /// the key has an invalid length
+    InvalidKeyLength,
+    /// the public key has an invalid length
+    InvalidPublicKeyLength,
+    /// the nonce has an invalid length
+    InvalidNonceLength,
+    /// the signature has an invalid length
+    InvalidSignatureLength,
+    /// the salt has an invalid length
+    InvalidSaltLength,
+    /// the RNG seed has an invalid length
+    InvalidSeedLength,
+    /// an input is too long
+    InvalidInputLength,
+    /// the buffer for a generated key doesn't have the size of the key
+    InvalidBufferSize,
+    /// the output buffer is too small
+    BufferTooSmall,
+    /// the output buffer is too large
+    BufferTooLarge,
+    /// more data than the algorithm can process with a key/nonce combo
+    TooMuchData,
+    /// the CPU cost is out of range
+    InvalidCpuCost,
+    /// the memory cost is out of range
+    InvalidMemoryCost,
+    /// the parallelism is out of range
+    InvalidParallelism,
+    /// the secret key is not a valid key for the algorithm
+    InvalidSecretKey,
+    /// the public key is not a valid key for the algorithm
+    InvalidPublicKey,
+    /// the data, tag or signature failed to authenticate
+    AuthenticationFailed,
+    /// the incremental state was already finalized
+    StateFinalized,
+    /// no algorithm with the requested id exists
+    UnknownAlgorithm,
+    /// the RNG failed to produce random bytes
+    RngFailure,
+    /// the range or set to choose a random value from is empty
+    EmptyRange,
+    /// a continuous health test of the RNG output tripped
+    HealthTestFailed,
+    /// the DRBG exhausted its reseed interval and has no entropy source to reseed from
+    ReseedRequired,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let description = match self {
+            Error::InvalidKeyLength => "Invalid key length",
+            Error::InvalidPublicKeyLength => "Invalid public key length",
+            Error::InvalidNonceLength => "Invalid nonce length",
+            Error::InvalidSignatureLength => "Invalid signature length",
+            Error::InvalidSaltLength => "Invalid salt length",
+            Error::InvalidSeedLength => "Invalid seed length",
+            Error::InvalidInputLength => "Invalid input length",
+            Error::InvalidBufferSize => "Invalid buffer size",
+            Error::BufferTooSmall => "Buffer is too small",
+            Error::BufferTooLarge => "Buffer is too large",
+            Error::TooMuchData => "Too much data",
+            Error::InvalidCpuCost => "Invalid CPU cost",
+            Error::InvalidMemoryCost => "Invalid memory cost",
+            Error::InvalidParallelism => "Invalid parallelism",
+            Error::InvalidSecretKey => "Invalid secret key",
+            Error::InvalidPublicKey => "Invalid public key",
+            Error::AuthenticationFailed => return write!(f, "Invalid Data"),
+            Error::StateFinalized => "State already finalized",
+            Error::UnknownAlgorithm => "Unknown algorithm id",
+            Error::RngFailure => "RNG failure",
+            Error::EmptyRange => "Empty range",
+            Error::HealthTestFailed => "RNG health test failed",
+            Error::ReseedRequired => "Reseed required",
+        };
+        write!(f, "Error: `{}`", description)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for std::io::Error {
+    fn from(error: Error) -> Self {
+        let kind = match error {
+            Error::AuthenticationFailed => std::io::ErrorKind::InvalidData,
+            Error::RngFailure => std::io::ErrorKind::Other,
+            _ => std::io::ErrorKind::InvalidInput,
+        };
+        std::io::Error::new(kind, error)
+    }
+}
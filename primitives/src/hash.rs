@@ -9,7 +9,8 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-use std::{error::Error, ops::Range};
+use crate::Error;
+use std::ops::Range;
 
 /// An information block describing a Hash.
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -27,11 +28,21 @@ pub trait Hash {
     /// Get the information block that describes the hash
     fn info(&self) -> HashInfo;
     /// hashes data and returns the hash length. `buf` contains the outgoing hashed data.  
-    fn hash(&self, buf: &mut [u8], data: &[u8]) -> Result<usize, Box<dyn Error + 'static>>;
+    fn hash(&self, buf: &mut [u8], data: &[u8]) -> Result<usize, Error>;
 }
 
 /// a variable length hash
 pub trait VarLenHash: Hash {
     /// hashes the data and returns the hash length. `buf` contains the outgoing hashed data.
-    fn var_len_hash(&self, buf: &mut [u8], data: &[u8]) -> Result<usize, Box<dyn Error + 'static>>;
+    fn var_len_hash(&self, buf: &mut [u8], data: &[u8]) -> Result<usize, Error>;
+}
+
+/// A stateful Hash that absorbs the data incrementally
+pub trait HashState {
+    /// Get the information block that describes the hash
+    fn info(&self) -> HashInfo;
+    /// absorbs the next chunk of `data` into the state.
+    fn update(&mut self, data: &[u8]) -> Result<(), Error>;
+    /// writes the hash into `buf` and returns the hash length. The state can not be used after it was finalized.
+    fn finalize(&mut self, buf: &mut [u8]) -> Result<usize, Error>;
 }
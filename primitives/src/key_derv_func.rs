@@ -9,7 +9,8 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-use std::{error::Error, ops::Range};
+use crate::Error;
+use std::ops::Range;
 
 /// A key derivation function info block
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -31,6 +32,5 @@ pub trait KeyDervFunc {
     /// returns the information block about the key derivation function
     fn info(&self) -> KeyDervFuncInfo;
     /// derive bytes from the base key with salt and info. Outputs to the buffer `buf`.
-    fn derive(&self, buf: &mut [u8], base_key: &[u8], salt: &[u8], info: &[u8])
-        -> Result<(), Box<dyn Error + 'static>>;
+    fn derive(&self, buf: &mut [u8], base_key: &[u8], salt: &[u8], info: &[u8]) -> Result<(), Error>;
 }
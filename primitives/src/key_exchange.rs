@@ -0,0 +1,39 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use std::ops::Range;
+
+use crate::{
+    rng::{PublicKeyGen, SecretKeyGen},
+    Error,
+};
+
+/// A block of information about the implemented key exchange
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct KeyExchangeInfo {
+    /// The key exchange id
+    pub id: &'static str,
+    /// A range of supported secret key lengths
+    pub secret_key_lens: Range<usize>,
+    /// A range of supported public key lengths
+    pub public_key_lens: Range<usize>,
+    /// A range of supported shared secret lengths
+    pub shared_secret_lens: Range<usize>,
+}
+
+/// A Diffie-Hellman key agreement.  Implements the `SecretKeyGen` and `PublicKeyGen` traits.
+pub trait KeyExchange: SecretKeyGen + PublicKeyGen {
+    /// returns the key exchange info block
+    fn info(&self) -> KeyExchangeInfo;
+    /// computes the shared secret from our secret key and their public key and returns the shared secret length.
+    /// `buf` contains the outgoing shared secret.
+    fn exchange(&self, buf: &mut [u8], secret_key: &[u8], public_key: &[u8]) -> Result<usize, Error>;
+}
@@ -17,13 +17,19 @@
 pub mod auth;
 /// Cipher
 pub mod cipher;
+/// Error
+mod error;
 /// Hash
 pub mod hash;
 /// Key derive function
 pub mod key_derv_func;
+/// Key exchange
+pub mod key_exchange;
 /// PBKDF
 pub mod pbkdf;
 /// Random Number Generator
 pub mod rng;
 /// Asymmetric Signing
 pub mod signing;
+
+pub use crate::error::Error;
@@ -9,7 +9,8 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-use std::{error::Error, ops::Range};
+use crate::Error;
+use std::ops::Range;
 
 /// information block describing the PBKDF implementation.
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -41,13 +42,7 @@ pub trait Pbkdf {
     /// returns the info of the PBKDF
     fn info(&self) -> PbkdfInfo;
     /// fills the buffer with bytes derived from the password parameterized by the CPU cost.
-    fn derive(
-        &self,
-        buf: &mut [u8],
-        password: &[u8],
-        salt: &[u8],
-        cpu_cost: u64,
-    ) -> Result<(), Box<dyn Error + 'static>>;
+    fn derive(&self, buf: &mut [u8], password: &[u8], salt: &[u8], cpu_cost: u64) -> Result<(), Error>;
 }
 
 /// A memory hardened PBKDF
@@ -61,5 +56,5 @@ pub trait StatelessPbkdf: Pbkdf {
         cpu_cost: u64,
         memory_cost: u64,
         parallelism: u64,
-    ) -> Result<(), Box<dyn Error + 'static>>;
+    ) -> Result<(), Error>;
 }
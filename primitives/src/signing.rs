@@ -9,8 +9,11 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-use crate::rng::{PublicKeyGen, SecretKeyGen};
-use std::{error::Error, ops::Range};
+use crate::{
+    rng::{PublicKeyGen, SecretKeyGen},
+    Error,
+};
+use std::ops::Range;
 
 /// Signature information block
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -29,7 +32,7 @@ pub trait Sign: SecretKeyGen + PublicKeyGen {
     /// returns info about the signer
     fn info(&self) -> SignInfo;
     /// signs data in the buffer using the secret key and returns the signature length.
-    fn sign(&self, buf: &mut [u8], data: &[u8], secret_key: &[u8]) -> Result<usize, Box<dyn Error + 'static>>;
+    fn sign(&self, buf: &mut [u8], data: &[u8], secret_key: &[u8]) -> Result<usize, Error>;
     /// verify the signature for the data with the public key.  Returns an error if the signature was invalid.
-    fn verify(&self, data: &[u8], sig: &[u8], public_key: &[u8]) -> Result<(), Box<dyn Error + 'static>>;
+    fn verify(&self, data: &[u8], sig: &[u8], public_key: &[u8]) -> Result<(), Error>;
 }
@@ -17,27 +17,37 @@ fn macos_secrandom() -> Option<&'static str> {
 }
 
 // checks if the current version of glibc supports the getrandom function
-#[cfg(target_os = "linux")]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
 fn linux_check_getrandom() -> Option<&'static str> {
     use std::{ffi::CStr, os::raw::c_char, str::FromStr};
     extern "C" {
         fn gnu_get_libc_version() -> *const c_char;
     }
 
-    let v: Vec<u8> = unsafe { CStr::from_ptr(gnu_get_libc_version()) }
+    // falls back to /dev/urandom if the version string can't be parsed
+    let version = unsafe { CStr::from_ptr(gnu_get_libc_version()) }
         .to_str()
-        .unwrap()
-        .split('.')
-        .map(|s| u8::from_str(s).unwrap())
-        .collect();
+        .unwrap_or_default();
+    let mut v = version.split('.').map(|s| u32::from_str(s).ok());
 
-    match (v[0], v[1]) {
-        (2..=255, 25..=255) => Some("USE_GETRANDOM"),
+    match (v.next().flatten(), v.next().flatten()) {
+        (Some(2), Some(25..=u32::MAX)) | (Some(3..=u32::MAX), Some(_)) => Some("USE_GETRANDOM"),
         _ => Some("USE_DEV_RANDOM"),
     }
 }
 
+// other C libraries don't have `gnu_get_libc_version`
+#[cfg(all(target_os = "linux", not(target_env = "gnu")))]
+fn linux_check_getrandom() -> Option<&'static str> {
+    Some("USE_DEV_RANDOM")
+}
+
 fn main() {
+    // the pure Rust backend doesn't need the C code
+    if std::env::var_os("CARGO_FEATURE_PURE_RUST").is_some() {
+        return;
+    }
+
     // determine which secure random number generator should be used.
     #[allow(unused_assignments)]
     let mut secure_random = None;
@@ -0,0 +1,172 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::OsRng;
+use crypto::{ChaCha20Ietf, Sha256};
+use primitives::{
+    rng::{DeterministicRng, SecureRng},
+    Error,
+};
+
+/// size of the seed and the ChaCha20 key
+pub const CHACHARNG_SEED: usize = 32;
+/// max bytes that are produced with one key before it is replaced
+const CHACHARNG_CHUNK: usize = 1024 * 1024;
+/// the nonce is fixed because every key is only used for a single chunk
+const CHACHARNG_NONCE: [u8; 12] = [0; 12];
+
+/// A ChaCha20 based CSPRNG with fast key erasure.
+///
+/// Every request (or every 1 MiB of it) is served from the ChaCha20 keystream starting at block 1, while block 0 of
+/// the keystream replaces the key. An attacker that learns the state can't recover any output that was produced
+/// before. Two generators with the same seed produce the same bytes for the same sequence of requests, but the
+/// output depends on how the bytes are requested: two calls for 32 bytes don't yield one call for 64 bytes.
+pub struct ChaChaRng {
+    key: [u8; CHACHARNG_SEED],
+}
+
+impl ChaChaRng {
+    /// creates a new generator from a 32 byte `seed`
+    pub fn from_seed(seed: &[u8]) -> Result<Self, Error> {
+        if seed.len() != CHACHARNG_SEED {
+            return Err(Error::InvalidSeedLength);
+        }
+
+        let mut key = [0; CHACHARNG_SEED];
+        key.copy_from_slice(seed);
+        Ok(Self { key })
+    }
+
+    /// creates a new generator that is seeded from `rng`
+    pub fn from_rng(rng: &mut dyn SecureRng) -> Result<Self, Error> {
+        let mut key = [0; CHACHARNG_SEED];
+        rng.random(&mut key)?;
+        Ok(Self { key })
+    }
+
+    /// creates a new generator that is seeded from the OS's secure RNG
+    pub fn new() -> Result<Self, Error> {
+        Self::from_rng(&mut OsRng)
+    }
+
+    /// creates a new boxed generator that is seeded from the OS's secure RNG
+    pub fn deterministic_rng() -> Result<Box<dyn DeterministicRng>, Error> {
+        Ok(Box::new(Self::new()?))
+    }
+}
+
+impl SecureRng for ChaChaRng {
+    /// fill the `buf` with random bytes.
+    fn random(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        for chunk in buf.chunks_mut(CHACHARNG_CHUNK) {
+            chunk.iter_mut().for_each(|b| *b = 0);
+            ChaCha20Ietf::xor(&self.key, &CHACHARNG_NONCE, 1, chunk);
+
+            // replace the key with keystream block 0
+            let mut next = [0; CHACHARNG_SEED];
+            ChaCha20Ietf::xor(&self.key, &CHACHARNG_NONCE, 0, &mut next);
+            self.key = next;
+            next.iter_mut().for_each(|b| *b = 0);
+        }
+        Ok(())
+    }
+}
+
+impl DeterministicRng for ChaChaRng {
+    /// mixes the `seed` into the key: `key = SHA-256(key || seed)`
+    fn reseed(&mut self, seed: &[u8]) -> Result<(), Error> {
+        let mut state = Sha256::hash_state();
+        state.update(&self.key)?;
+        state.update(seed)?;
+        state.finalize(&mut self.key)?;
+        Ok(())
+    }
+}
+
+impl Drop for ChaChaRng {
+    fn drop(&mut self) {
+        self.key.iter_mut().for_each(|b| *b = 0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ChaChaRng;
+    use primitives::{
+        rng::{DeterministicRng, SecureRng},
+        Error,
+    };
+
+    /// RFC 7539 A.1 test vector #2: the keystream block 1 for the all zero key and nonce
+    const ZERO_SEED_BLOCK: &str = "9f07e7be5551387a98ba977c732d080dcb0f29a048e3656912c6533e32ee7aed\
+                                   29b721769ce64e43d57133b074d839d531ed1f28510afb45ace10a1f4b794d6f";
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_known_answer() {
+        let mut buf = [0; 64];
+        ChaChaRng::from_seed(&[0; 32]).unwrap().random(&mut buf).unwrap();
+        assert_eq!(hex(&buf), ZERO_SEED_BLOCK);
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let (mut a, mut b) = (
+            ChaChaRng::from_seed(&[7; 32]).unwrap(),
+            ChaChaRng::from_seed(&[7; 32]).unwrap(),
+        );
+        for len in [0, 1, 63, 64, 65, 1024 * 1024 + 1].iter() {
+            let (mut buf_a, mut buf_b) = (vec![0; *len], vec![0; *len]);
+            a.random(&mut buf_a).unwrap();
+            b.random(&mut buf_b).unwrap();
+            assert_eq!(buf_a, buf_b);
+        }
+
+        a.reseed(b"seed").unwrap();
+        b.reseed(b"seed").unwrap();
+        let (mut buf_a, mut buf_b) = ([0; 32], [0; 32]);
+        a.random(&mut buf_a).unwrap();
+        b.random(&mut buf_b).unwrap();
+        assert_eq!(buf_a, buf_b);
+    }
+
+    #[test]
+    fn test_key_erasure() {
+        let mut rng = ChaChaRng::from_seed(&[0; 32]).unwrap();
+        let (mut first, mut second) = ([0; 64], [0; 64]);
+        rng.random(&mut first).unwrap();
+        rng.random(&mut second).unwrap();
+        assert_ne!(first, second);
+
+        let mut reseeded = ChaChaRng::from_seed(&[0; 32]).unwrap();
+        reseeded.reseed(b"seed").unwrap();
+        reseeded.random(&mut second).unwrap();
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_invalid_seed() {
+        assert_eq!(ChaChaRng::from_seed(&[0; 31]).err(), Some(Error::InvalidSeedLength));
+        assert_eq!(ChaChaRng::from_seed(&[0; 33]).err(), Some(Error::InvalidSeedLength));
+    }
+
+    #[test]
+    fn test_os_seeded() {
+        let (mut a, mut b) = (ChaChaRng::new().unwrap(), ChaChaRng::new().unwrap());
+        let (mut buf_a, mut buf_b) = ([0; 32], [0; 32]);
+        a.random(&mut buf_a).unwrap();
+        b.random(&mut buf_b).unwrap();
+        assert_ne!(buf_a, buf_b);
+    }
+}
@@ -0,0 +1,270 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+//! Unbiased random values on top of a `SecureRng`: uniform integers in a range, choosing from and shuffling slices,
+//! and generating passwords and diceware-style passphrases together with their entropy.
+
+use primitives::{rng::SecureRng, Error};
+use std::ops::Range;
+
+/// the BIP-39 English word list with 2048 words, so every word adds 11 bits of entropy
+const WORDLIST: &str = include_str!("bip39_english.txt");
+/// number of words in the word list
+pub const WORDLIST_LEN: usize = 2048;
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+/// characters that are easy to confuse with each other
+const AMBIGUOUS: &str = "0O1lI|`'\"";
+
+/// A generated password or passphrase and its entropy in bits
+#[derive(Debug, Clone, PartialEq)]
+pub struct Password {
+    /// the password or passphrase
+    pub value: String,
+    /// the entropy of the generation process in bits
+    pub entropy: f64,
+}
+
+/// The character classes and the length of a generated password
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PasswordConfig {
+    /// number of characters
+    pub length: usize,
+    /// use `a-z`
+    pub lowercase: bool,
+    /// use `A-Z`
+    pub uppercase: bool,
+    /// use `0-9`
+    pub digits: bool,
+    /// use the printable ASCII symbols
+    pub symbols: bool,
+    /// don't use characters that are easy to confuse like `0`, `O`, `1`, `l` and `I`
+    pub exclude_ambiguous: bool,
+}
+
+impl Default for PasswordConfig {
+    /// 20 characters from all classes
+    fn default() -> Self {
+        Self {
+            length: 20,
+            lowercase: true,
+            uppercase: true,
+            digits: true,
+            symbols: true,
+            exclude_ambiguous: false,
+        }
+    }
+}
+
+impl PasswordConfig {
+    /// returns the characters of the enabled classes
+    fn charset(&self) -> Vec<char> {
+        [
+            (self.lowercase, LOWERCASE),
+            (self.uppercase, UPPERCASE),
+            (self.digits, DIGITS),
+            (self.symbols, SYMBOLS),
+        ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .flat_map(|(_, class)| class.chars())
+        .filter(|c| !self.exclude_ambiguous || !AMBIGUOUS.contains(*c))
+        .collect()
+    }
+}
+
+/// returns a uniformly distributed integer in the `range` without modulo bias. Fails with `Error::EmptyRange` if the
+/// range is empty.
+pub fn uniform(rng: &mut dyn SecureRng, range: Range<u64>) -> Result<u64, Error> {
+    if range.start >= range.end {
+        return Err(Error::EmptyRange);
+    }
+    let span = range.end - range.start;
+
+    // rejects the values above the largest multiple of `span`
+    let zone = u64::MAX - (u64::MAX - span + 1) % span;
+    loop {
+        let mut sample = [0; 8];
+        rng.random(&mut sample)?;
+
+        let sample = u64::from_le_bytes(sample);
+        if sample <= zone {
+            return Ok(range.start + sample % span);
+        }
+    }
+}
+
+/// returns a uniformly distributed index below `len`
+fn index(rng: &mut dyn SecureRng, len: usize) -> Result<usize, Error> {
+    Ok(uniform(rng, 0..len as u64)? as usize)
+}
+
+/// returns a uniformly chosen element of the `slice`. Fails with `Error::EmptyRange` if the slice is empty.
+pub fn choose<'a, T>(rng: &mut dyn SecureRng, slice: &'a [T]) -> Result<&'a T, Error> {
+    Ok(&slice[index(rng, slice.len())?])
+}
+
+/// shuffles the `slice` in place with the Fisher–Yates shuffle, so every permutation is equally likely.
+pub fn shuffle<T>(rng: &mut dyn SecureRng, slice: &mut [T]) -> Result<(), Error> {
+    for i in (1..slice.len()).rev() {
+        slice.swap(i, index(rng, i + 1)?);
+    }
+    Ok(())
+}
+
+/// generates a password with the length and the character classes of the `config`. Every character is chosen
+/// uniformly from the union of the classes. Fails with `Error::EmptyRange` if no character is left to choose from.
+pub fn password(rng: &mut dyn SecureRng, config: &PasswordConfig) -> Result<Password, Error> {
+    let charset = config.charset();
+    if charset.is_empty() {
+        return Err(Error::EmptyRange);
+    }
+
+    let value = (0..config.length)
+        .map(|_| choose(rng, &charset).copied())
+        .collect::<Result<String, Error>>()?;
+    Ok(Password {
+        value,
+        entropy: config.length as f64 * (charset.len() as f64).log2(),
+    })
+}
+
+/// generates a diceware-style passphrase of `words` words from the BIP-39 English word list that are joined by the
+/// `separator`.
+pub fn passphrase(rng: &mut dyn SecureRng, words: usize, separator: &str) -> Result<Password, Error> {
+    let wordlist: Vec<&str> = WORDLIST.lines().collect();
+
+    let value = (0..words)
+        .map(|_| choose(rng, &wordlist).copied())
+        .collect::<Result<Vec<&str>, Error>>()?
+        .join(separator);
+    Ok(Password {
+        value,
+        entropy: words as f64 * (WORDLIST_LEN as f64).log2(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{choose, passphrase, password, shuffle, uniform, PasswordConfig, WORDLIST, WORDLIST_LEN};
+    use crate::{ChaChaRng, OsRng};
+    use primitives::Error;
+
+    /// test that the `counts` are within 5% of their average
+    fn test_uniform_dist(counts: &[usize]) {
+        let avg = counts.iter().sum::<usize>() as f64 / counts.len() as f64;
+        counts.iter().for_each(|count| {
+            assert!((*count as f64 - avg).abs() < avg * 0.05, "{:?} is not uniform", counts);
+        });
+    }
+
+    #[test]
+    fn test_uniform() {
+        let mut rng = ChaChaRng::new().unwrap();
+
+        // a span of 3 would be biased with `sample % 3` on a single byte
+        let mut counts = vec![0; 3];
+        for _ in 0..60_000 {
+            let value = uniform(&mut rng, 10..13).unwrap();
+            counts[(value - 10) as usize] += 1;
+        }
+        test_uniform_dist(&counts);
+
+        assert_eq!(uniform(&mut rng, 7..8).unwrap(), 7);
+        assert_eq!(uniform(&mut rng, u64::MAX - 1..u64::MAX).unwrap(), u64::MAX - 1);
+        uniform(&mut rng, 0..u64::MAX).unwrap();
+        assert_eq!(uniform(&mut rng, 5..5), Err(Error::EmptyRange));
+    }
+
+    #[test]
+    fn test_choose() {
+        let mut rng = ChaChaRng::new().unwrap();
+        let empty: [u8; 0] = [];
+        assert_eq!(choose(&mut rng, &empty), Err(Error::EmptyRange));
+        assert_eq!(choose(&mut rng, &[1]), Ok(&1));
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut rng = ChaChaRng::new().unwrap();
+
+        // all 6 permutations of 3 elements are equally likely
+        let mut counts = vec![0; 6];
+        for _ in 0..60_000 {
+            let mut slice = [0, 1, 2];
+            shuffle(&mut rng, &mut slice).unwrap();
+            let permutation = match slice {
+                [0, 1, 2] => 0,
+                [0, 2, 1] => 1,
+                [1, 0, 2] => 2,
+                [1, 2, 0] => 3,
+                [2, 0, 1] => 4,
+                [2, 1, 0] => 5,
+                _ => panic!("{:?} is not a permutation", slice),
+            };
+            counts[permutation] += 1;
+        }
+        test_uniform_dist(&counts);
+
+        shuffle(&mut rng, &mut [0u8; 0]).unwrap();
+    }
+
+    #[test]
+    fn test_password() {
+        let config = PasswordConfig {
+            length: 16,
+            lowercase: false,
+            symbols: false,
+            exclude_ambiguous: true,
+            ..PasswordConfig::default()
+        };
+        let generated = password(&mut OsRng, &config).unwrap();
+        assert_eq!(generated.value.len(), 16);
+        assert!(generated
+            .value
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()));
+        assert!(!generated.value.contains(|c| "0O1I".contains(c)));
+        // 24 letters and 8 digits
+        assert!((generated.entropy - 16.0 * 5.0).abs() < 1e-9);
+
+        let generated = password(&mut OsRng, &PasswordConfig::default()).unwrap();
+        assert!(generated.value.chars().all(|c| c.is_ascii_graphic()));
+        assert!((generated.entropy - 20.0 * 94f64.log2()).abs() < 1e-9);
+
+        let config = PasswordConfig {
+            lowercase: false,
+            uppercase: false,
+            digits: false,
+            symbols: false,
+            ..PasswordConfig::default()
+        };
+        assert_eq!(password(&mut OsRng, &config), Err(Error::EmptyRange));
+    }
+
+    #[test]
+    fn test_passphrase() {
+        let words: Vec<&str> = WORDLIST.lines().collect();
+        assert_eq!(words.len(), WORDLIST_LEN);
+        assert!(words.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let generated = passphrase(&mut OsRng, 6, "-").unwrap();
+        assert_eq!(generated.value.split('-').count(), 6);
+        assert!(generated
+            .value
+            .split('-')
+            .all(|word| words.binary_search(&word).is_ok()));
+        assert!((generated.entropy - 66.0).abs() < 1e-9);
+    }
+}
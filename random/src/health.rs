@@ -0,0 +1,261 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use primitives::{rng::SecureRng, Error};
+
+/// the false positive probability of both tests is 2^-40 per sample
+const HEALTH_ALPHA_LOG2: u32 = 40;
+/// window size of the adaptive proportion test for non-binary samples
+const HEALTH_APT_WINDOW: usize = 512;
+/// number of samples that are tested and discarded at startup
+const HEALTH_STARTUP: usize = 1024;
+
+/// the repetition count test cutoff: `1 + ceil(-log2(alpha) / H)`
+fn rct_cutoff(min_entropy: u32) -> usize {
+    1 + ((HEALTH_ALPHA_LOG2 + min_entropy - 1) / min_entropy) as usize
+}
+
+/// the adaptive proportion test cutoff: `1 + CRITBINOM(W, 2^-H, 1 - alpha)`
+fn apt_cutoff(min_entropy: u32) -> usize {
+    let (p, alpha) = (0.5f64.powi(min_entropy as i32), 0.5f64.powi(HEALTH_ALPHA_LOG2 as i32));
+
+    // the binomial distribution of the number of samples that match the first sample of a window
+    let mut pmf = vec![(1.0 - p).powi(HEALTH_APT_WINDOW as i32)];
+    for k in 0..HEALTH_APT_WINDOW {
+        let next = pmf[k] * (HEALTH_APT_WINDOW - k) as f64 / (k + 1) as f64 * p / (1.0 - p);
+        pmf.push(next);
+    }
+
+    // the smallest count whose upper tail probability doesn't exceed alpha
+    let mut tail = 0.0;
+    for k in (0..=HEALTH_APT_WINDOW).rev() {
+        if tail + pmf[k] > alpha {
+            return k + 1;
+        }
+        tail += pmf[k];
+    }
+    1
+}
+
+/// A wrapper that runs the continuous health tests of NIST SP 800-90B section 4.4 on every byte that the wrapped RNG
+/// produces.
+///
+/// Each byte is a sample for the repetition count test, which detects a source that got stuck on one value, and the
+/// adaptive proportion test, which detects a source that produces one value far too often within a window of 512
+/// samples. The cutoffs are derived from the claimed min-entropy per byte with a false positive probability of 2^-40.
+/// If a test trips, the output is wiped, `Error::HealthTestFailed` is returned and all following requests fail until
+/// `reset` is called.
+pub struct HealthCheckedRng<R: SecureRng> {
+    rng: R,
+    rct_cutoff: usize,
+    apt_cutoff: usize,
+    rct_value: u8,
+    rct_count: usize,
+    apt_value: u8,
+    apt_count: usize,
+    apt_samples: usize,
+    failed: bool,
+}
+
+impl<R: SecureRng> HealthCheckedRng<R> {
+    /// wraps the `rng` and claims full entropy (8 bits per byte), which holds for conditioned sources like the OS's
+    /// secure RNG. The startup tests are run on 1024 bytes that are discarded afterwards.
+    pub fn new(rng: R) -> Result<Self, Error> {
+        Self::with_min_entropy(rng, 8)
+    }
+
+    /// wraps the `rng` that provides at least `min_entropy` bits per byte. Panics if `min_entropy` is not in `1..=8`.
+    pub fn with_min_entropy(rng: R, min_entropy: u32) -> Result<Self, Error> {
+        assert!(
+            (1..=8).contains(&min_entropy),
+            "The min-entropy must be between 1 and 8 bits per byte"
+        );
+
+        let mut this = Self {
+            rng,
+            rct_cutoff: rct_cutoff(min_entropy),
+            apt_cutoff: apt_cutoff(min_entropy),
+            rct_value: 0,
+            rct_count: 0,
+            apt_value: 0,
+            apt_count: 0,
+            apt_samples: 0,
+            failed: false,
+        };
+        this.reset()?;
+        Ok(this)
+    }
+
+    /// clears a failure and the test state and reruns the startup tests
+    pub fn reset(&mut self) -> Result<(), Error> {
+        self.rct_count = 0;
+        self.apt_samples = 0;
+        self.failed = false;
+
+        let mut startup = [0; HEALTH_STARTUP];
+        self.random(&mut startup)
+    }
+
+    /// returns the repetition count and the adaptive proportion test cutoffs
+    pub fn cutoffs(&self) -> (usize, usize) {
+        (self.rct_cutoff, self.apt_cutoff)
+    }
+
+    /// returns the wrapped RNG
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+
+    /// runs both tests on a `sample` and returns `false` if one of them tripped
+    fn test(&mut self, sample: u8) -> bool {
+        // repetition count test
+        if self.rct_count > 0 && sample == self.rct_value {
+            self.rct_count += 1;
+        } else {
+            self.rct_value = sample;
+            self.rct_count = 1;
+        }
+
+        // adaptive proportion test
+        if self.apt_samples == 0 {
+            self.apt_value = sample;
+            self.apt_count = 0;
+        }
+        if sample == self.apt_value {
+            self.apt_count += 1;
+        }
+        self.apt_samples = (self.apt_samples + 1) % HEALTH_APT_WINDOW;
+
+        self.rct_count < self.rct_cutoff && self.apt_count < self.apt_cutoff
+    }
+}
+
+impl<R: SecureRng> SecureRng for HealthCheckedRng<R> {
+    /// fill the `buf` with random bytes that passed the health tests.
+    fn random(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        if !self.failed {
+            self.rng.random(buf)?;
+            self.failed = !buf.iter().all(|sample| self.test(*sample));
+        }
+
+        if self.failed {
+            buf.iter_mut().for_each(|b| *b = 0);
+            return Err(Error::HealthTestFailed);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{apt_cutoff, rct_cutoff, HealthCheckedRng};
+    use crate::OsRng;
+    use primitives::{rng::SecureRng, Error};
+
+    // rng that produces the bytes of a pattern function
+    struct PatternRng<F: FnMut() -> u8>(F);
+    impl<F: FnMut() -> u8> SecureRng for PatternRng<F> {
+        fn random(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+            buf.iter_mut().for_each(|b| *b = (self.0)());
+            Ok(())
+        }
+    }
+
+    // rng that cycles through 1..=255 and gets stuck on zero after `healthy` bytes
+    fn stuck_rng(mut healthy: usize) -> PatternRng<impl FnMut() -> u8> {
+        let mut counter = 0u8;
+        PatternRng(move || {
+            counter = counter % 255 + 1;
+            healthy = healthy.saturating_sub(1);
+            if healthy > 0 {
+                counter
+            } else {
+                0
+            }
+        })
+    }
+
+    #[test]
+    fn test_cutoffs() {
+        // the cutoffs for alpha = 2^-40, with alpha = 2^-20 the APT cutoffs match table 2 of SP 800-90B
+        let cutoffs: Vec<_> = (1..=8).map(|h| (rct_cutoff(h), apt_cutoff(h))).collect();
+        assert_eq!(
+            cutoffs,
+            vec![
+                (41, 336),
+                (21, 201),
+                (15, 123),
+                (11, 78),
+                (9, 51),
+                (8, 35),
+                (7, 26),
+                (6, 19)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_os_rng() {
+        let mut rng = HealthCheckedRng::new(OsRng).unwrap();
+        let mut buf = vec![0; 16 * 1024 * 1024];
+        rng.random(&mut buf).unwrap();
+    }
+
+    #[test]
+    fn test_startup() {
+        assert_eq!(HealthCheckedRng::new(stuck_rng(0)).err(), Some(Error::HealthTestFailed));
+        assert_eq!(
+            HealthCheckedRng::new(stuck_rng(1000)).err(),
+            Some(Error::HealthTestFailed)
+        );
+    }
+
+    #[test]
+    fn test_repetition_count() {
+        let mut rng = HealthCheckedRng::new(stuck_rng(2000)).unwrap();
+        let mut buf = [0; 1024];
+        assert_eq!(rng.random(&mut buf), Err(Error::HealthTestFailed));
+        assert!(buf.iter().all(|b| *b == 0));
+
+        // the failure is latched until the rng is reset
+        let mut rng = HealthCheckedRng::new(stuck_rng(1025)).unwrap();
+        assert_eq!(rng.random(&mut buf[..5]), Ok(()));
+        assert_eq!(rng.random(&mut buf[..1]), Err(Error::HealthTestFailed));
+        assert_eq!(rng.random(&mut buf[..1]), Err(Error::HealthTestFailed));
+        assert_eq!(rng.reset(), Err(Error::HealthTestFailed));
+    }
+
+    #[test]
+    fn test_adaptive_proportion() {
+        // every second byte is zero, so there are no repetitions but far too many zeros
+        let mut counter = 0u8;
+        let alternating = PatternRng(move || {
+            counter = counter.wrapping_add(1);
+            if counter % 2 == 1 {
+                0
+            } else {
+                counter
+            }
+        });
+        assert_eq!(HealthCheckedRng::new(alternating).err(), Some(Error::HealthTestFailed));
+
+        // a source with 1 bit of min-entropy per byte passes with a matching claim
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let biased = PatternRng(move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state & 1) as u8
+        });
+        let mut rng = HealthCheckedRng::with_min_entropy(biased, 1).unwrap();
+        rng.random(&mut [0; 65536]).unwrap();
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::OsRng;
+use crypto::HmacSha256;
+use primitives::{
+    rng::{DeterministicRng, SecureRng},
+    Error,
+};
+
+/// security strength in bytes and the min length of the entropy input
+pub const HMACDRBG_SECURITY_STRENGTH: usize = 32;
+/// min length of the instantiation nonce
+pub const HMACDRBG_NONCE_MIN: usize = 16;
+/// max bytes that can be requested with a single generate call (2^19 bits)
+pub const HMACDRBG_MAX_REQUEST: usize = 65536;
+/// max length of entropy input, personalization strings and additional input (2^35 bits)
+#[cfg(target_pointer_width = "64")]
+pub const HMACDRBG_MAX_INPUT: usize = 4_294_967_296;
+#[cfg(target_pointer_width = "32")]
+pub const HMACDRBG_MAX_INPUT: usize = usize::MAX;
+/// max generate calls between two reseeds (2^48)
+pub const HMACDRBG_RESEED_INTERVAL: u64 = 1 << 48;
+
+/// size of the HMAC-SHA256 key and chaining value
+const HMACDRBG_LEN: usize = 32;
+
+/// An HMAC-DRBG with SHA-256 as specified in NIST SP 800-90A Rev. 1 section 10.1.2.
+///
+/// A DRBG that was created with `new` owns an entropy source. It reseeds itself from that source once the reseed
+/// interval is exhausted and, with prediction resistance, before every generate call. A DRBG that was created with
+/// `instantiate` has no entropy source and `generate` returns `Error::ReseedRequired` until it is reseeded.
+pub struct HmacDrbg {
+    key: [u8; HMACDRBG_LEN],
+    value: [u8; HMACDRBG_LEN],
+    reseed_counter: u64,
+    reseed_interval: u64,
+    prediction_resistance: bool,
+    source: Option<Box<dyn SecureRng>>,
+}
+
+impl HmacDrbg {
+    /// instantiates a DRBG from caller provided `entropy`, `nonce` and `personalization` string. The DRBG has no
+    /// entropy source and therefore can't provide prediction resistance.
+    pub fn instantiate(entropy: &[u8], nonce: &[u8], personalization: &[u8]) -> Result<Self, Error> {
+        verify_entropy(entropy)?;
+        if nonce.len() < HMACDRBG_NONCE_MIN || nonce.len() > HMACDRBG_MAX_INPUT {
+            return Err(Error::InvalidNonceLength);
+        }
+        if personalization.len() > HMACDRBG_MAX_INPUT {
+            return Err(Error::InvalidInputLength);
+        }
+
+        let mut this = Self {
+            key: [0; HMACDRBG_LEN],
+            value: [1; HMACDRBG_LEN],
+            reseed_counter: 1,
+            reseed_interval: HMACDRBG_RESEED_INTERVAL,
+            prediction_resistance: false,
+            source: None,
+        };
+        this.update(&[entropy, nonce, personalization])?;
+        Ok(this)
+    }
+
+    /// instantiates a DRBG with entropy and nonce from the `source` which is also used for reseeding. With
+    /// `prediction_resistance` the DRBG reseeds from the `source` before every generate call.
+    pub fn new(
+        mut source: Box<dyn SecureRng>,
+        personalization: &[u8],
+        prediction_resistance: bool,
+    ) -> Result<Self, Error> {
+        let (mut entropy, mut nonce) = ([0; HMACDRBG_SECURITY_STRENGTH], [0; HMACDRBG_NONCE_MIN]);
+        source.random(&mut entropy)?;
+        source.random(&mut nonce)?;
+
+        let mut this = Self::instantiate(&entropy, &nonce, personalization)?;
+        this.prediction_resistance = prediction_resistance;
+        this.source = Some(source);
+        Ok(this)
+    }
+
+    /// instantiates a DRBG that uses the OS's secure RNG as its entropy source
+    pub fn from_os_rng(personalization: &[u8], prediction_resistance: bool) -> Result<Self, Error> {
+        Self::new(Box::new(OsRng), personalization, prediction_resistance)
+    }
+
+    /// sets the number of generate calls after which a reseed is required. The interval is capped at
+    /// `HMACDRBG_RESEED_INTERVAL`.
+    pub fn with_reseed_interval(mut self, reseed_interval: u64) -> Self {
+        self.reseed_interval = reseed_interval.min(HMACDRBG_RESEED_INTERVAL);
+        self
+    }
+
+    /// reseeds the DRBG with caller provided `entropy` and `additional` input
+    pub fn reseed_with(&mut self, entropy: &[u8], additional: &[u8]) -> Result<(), Error> {
+        verify_entropy(entropy)?;
+        if additional.len() > HMACDRBG_MAX_INPUT {
+            return Err(Error::InvalidInputLength);
+        }
+
+        self.update(&[entropy, additional])?;
+        self.reseed_counter = 1;
+        Ok(())
+    }
+
+    /// fills `buf` with up to `HMACDRBG_MAX_REQUEST` pseudo random bytes after mixing in the `additional` input
+    pub fn generate(&mut self, buf: &mut [u8], additional: &[u8]) -> Result<(), Error> {
+        if buf.len() > HMACDRBG_MAX_REQUEST {
+            return Err(Error::BufferTooLarge);
+        }
+        if additional.len() > HMACDRBG_MAX_INPUT {
+            return Err(Error::InvalidInputLength);
+        }
+
+        // with prediction resistance (or an exhausted interval) the additional input is consumed by the reseed
+        let mut additional = additional;
+        if self.prediction_resistance || self.reseed_counter > self.reseed_interval {
+            let mut entropy = [0; HMACDRBG_SECURITY_STRENGTH];
+            self.source
+                .as_mut()
+                .ok_or(Error::ReseedRequired)?
+                .random(&mut entropy)?;
+            self.reseed_with(&entropy, additional)?;
+            additional = &[];
+        }
+
+        if !additional.is_empty() {
+            self.update(&[additional])?;
+        }
+        for chunk in buf.chunks_mut(HMACDRBG_LEN) {
+            self.value = self.hmac(&[&self.value])?;
+            chunk.copy_from_slice(&self.value[..chunk.len()]);
+        }
+        self.update(&[additional])?;
+        self.reseed_counter += 1;
+        Ok(())
+    }
+
+    /// the HMAC_DRBG_Update function over the concatenation of the `data` parts
+    fn update(&mut self, data: &[&[u8]]) -> Result<(), Error> {
+        let provided = data.iter().any(|part| !part.is_empty());
+        for round in [0u8, 1].iter().take(if provided { 2 } else { 1 }) {
+            let mut parts = vec![&self.value[..], std::slice::from_ref(round)];
+            parts.extend_from_slice(data);
+            self.key = self.hmac(&parts)?;
+            self.value = self.hmac(&[&self.value])?;
+        }
+        Ok(())
+    }
+
+    /// computes HMAC-SHA256 with the current key over the concatenation of the `data` parts
+    fn hmac(&self, data: &[&[u8]]) -> Result<[u8; HMACDRBG_LEN], Error> {
+        let mut state = HmacSha256::mac_state(&self.key)?;
+        for part in data {
+            state.update(part)?;
+        }
+
+        let mut mac = [0; HMACDRBG_LEN];
+        state.finalize(&mut mac)?;
+        Ok(mac)
+    }
+}
+
+/// validates the length of an entropy input
+fn verify_entropy(entropy: &[u8]) -> Result<(), Error> {
+    if entropy.len() < HMACDRBG_SECURITY_STRENGTH || entropy.len() > HMACDRBG_MAX_INPUT {
+        return Err(Error::InvalidSeedLength);
+    }
+    Ok(())
+}
+
+impl SecureRng for HmacDrbg {
+    /// fill the `buf` with random bytes in requests of up to `HMACDRBG_MAX_REQUEST` bytes.
+    fn random(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        for chunk in buf.chunks_mut(HMACDRBG_MAX_REQUEST) {
+            self.generate(chunk, &[])?;
+        }
+        Ok(())
+    }
+}
+
+impl DeterministicRng for HmacDrbg {
+    /// reseeds the DRBG with the `seed` as entropy input
+    fn reseed(&mut self, seed: &[u8]) -> Result<(), Error> {
+        self.reseed_with(seed, &[])
+    }
+}
+
+impl Drop for HmacDrbg {
+    fn drop(&mut self) {
+        self.key.iter_mut().for_each(|b| *b = 0);
+        self.value.iter_mut().for_each(|b| *b = 0);
+    }
+}
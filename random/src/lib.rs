@@ -9,29 +9,54 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
-pub use primitives;
-use primitives::rng::SecureRng;
-use std::{
-    error::Error,
-    fmt::{Display, Formatter, Result as FmtResult},
-};
+//! Rust Bindings to major C random generator headers.  A library that creates secure random number generators.
+//!
+//! This crate implements the RNG (random number generator) traits defined in the primitives crate to describe a secure
+//! random number generator. C code was used when creating this crate because all of the major platforms feature battle
+//! tested RNG libraries. This C code is bridged with Rust using CC and Rust’s FFI (foreign function
+//! interface). This crate supports windows, mac, linux, iOS and a few BSD flavors. With the `pure-rust` feature the
+//! C code is replaced by the `getrandom` syscall with a `/dev/urandom` fallback on unix targets.
+//!
+//! The `ChaChaRng` is a fast userspace CSPRNG that implements the `DeterministicRng` trait and can be seeded from the
+//! `OsRng` or from a fixed seed for reproducible output. The `HmacDrbg` is an HMAC-DRBG with SHA-256 as specified in
+//! NIST SP 800-90A with reseed counters, personalization strings and optional prediction resistance. The
+//! `HealthCheckedRng` runs the continuous health tests of NIST SP 800-90B on the output of any `SecureRng` and the
+//! `ReseedingRng` serves requests from a fast deterministic generator that it periodically reseeds from the `OsRng`.
+//!
+//! With the `rand_core` feature the `OsRng` implements `rand_core::RngCore` and `CryptoRng`, and the `AsRngCore` and
+//! `AsSecureRng` adapters convert between `SecureRng` and `rand_core` generators.
+//!
+//! The `distributions` module turns the bytes of any `SecureRng` into unbiased uniform integers, choices, shuffles,
+//! passwords and passphrases.
 
-/// Rust Bindings to major C random generator headers.  A library that creates secure random number generators.
-///
-/// This crate implements the RNG (random number generator) traits defined in the primitives crate to describe a secure
-/// random number generator. C code was used when creating this crate because all of the major platforms feature battle
-/// tested RNG libraries. This C code is bridged with Rust using CC and Rust’s FFI (foreign function
-/// interface). This crate supports windows, mac, linux, iOS and a few BSD flavors.
+mod chacha;
+pub mod distributions;
+mod health;
+mod hmac_drbg;
+#[cfg(feature = "pure-rust")]
+mod pure;
+#[cfg(feature = "rand_core")]
+mod rand;
+mod reseeding;
 
-/// Error for dealing with errors from the OS RNG.
-#[derive(Debug, Eq, PartialEq, Copy, Clone)]
-pub struct OsRandomErr;
-impl Display for OsRandomErr {
-    fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        write!(f, "{:?}", self)
-    }
-}
-impl Error for OsRandomErr {}
+#[cfg(feature = "rand_core")]
+pub use crate::rand::{AsRngCore, AsSecureRng};
+pub use crate::{
+    chacha::{ChaChaRng, CHACHARNG_SEED},
+    health::HealthCheckedRng,
+    hmac_drbg::{
+        HmacDrbg, HMACDRBG_MAX_INPUT, HMACDRBG_MAX_REQUEST, HMACDRBG_NONCE_MIN, HMACDRBG_RESEED_INTERVAL,
+        HMACDRBG_SECURITY_STRENGTH,
+    },
+    reseeding::{ReseedingRng, RESEEDINGRNG_BYTES, RESEEDINGRNG_INTERVAL},
+};
+pub use primitives;
+use primitives::{rng::SecureRng, Error};
+#[cfg(feature = "rand_core")]
+pub use rand_core;
+
+#[cfg(feature = "pure-rust")]
+use crate::pure::os_random;
 
 /// an interface for the OS's secure RNG
 pub struct OsRng;
@@ -43,17 +68,23 @@ impl OsRng {
 
 impl SecureRng for OsRng {
     /// fill the `buf` with random bytes.
-    fn random(&mut self, buf: &mut [u8]) -> Result<(), Box<dyn Error + 'static>> {
-        // the API bridge
-        extern "C" {
-            fn os_random_secrandom(buf: *mut u8, len: usize) -> u8;
-        }
+    fn random(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        os_random(buf)
+    }
+}
 
-        // call to the c code
-        match unsafe { os_random_secrandom(buf.as_mut_ptr(), buf.len()) } {
-            0 => Ok(()),
-            _ => Err(OsRandomErr.into()),
-        }
+/// fills the `buf` with random bytes from the C backend.
+#[cfg(not(feature = "pure-rust"))]
+fn os_random(buf: &mut [u8]) -> Result<(), Error> {
+    // the API bridge
+    extern "C" {
+        fn os_random_secrandom(buf: *mut u8, len: usize) -> u8;
+    }
+
+    // call to the c code
+    match unsafe { os_random_secrandom(buf.as_mut_ptr(), buf.len()) } {
+        0 => Ok(()),
+        _ => Err(Error::RngFailure),
     }
 }
 
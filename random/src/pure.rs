@@ -0,0 +1,99 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use primitives::Error;
+use std::{
+    fs::File,
+    io::{ErrorKind, Read},
+};
+
+/// fills the `buf` with the `getrandom` syscall. Returns `None` if the kernel doesn't provide the syscall (or a
+/// seccomp filter denies it) before any bytes were written.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn getrandom(buf: &mut [u8]) -> Option<Result<(), Error>> {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    // remembers that the syscall is unavailable so that it isn't tried again
+    static UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+    if UNAVAILABLE.load(Ordering::Relaxed) {
+        return None;
+    }
+
+    let mut filled = 0;
+    while filled < buf.len() {
+        let rest = &mut buf[filled..];
+        let ret = unsafe { libc::syscall(libc::SYS_getrandom, rest.as_mut_ptr(), rest.len(), 0) };
+        match ret {
+            ret if ret > 0 => filled += ret as usize,
+            0 => return Some(Err(Error::RngFailure)),
+            _ => match std::io::Error::last_os_error().raw_os_error() {
+                Some(libc::EINTR) => continue,
+                Some(libc::ENOSYS) | Some(libc::EPERM) if filled == 0 => {
+                    UNAVAILABLE.store(true, Ordering::Relaxed);
+                    return None;
+                }
+                _ => return Some(Err(Error::RngFailure)),
+            },
+        }
+    }
+    Some(Ok(()))
+}
+
+/// fills the `buf` from `/dev/urandom` and retries interrupted and short reads.
+fn urandom(buf: &mut [u8]) -> Result<(), Error> {
+    let mut file = File::open("/dev/urandom").map_err(|_| Error::RngFailure)?;
+
+    let mut filled = 0;
+    while filled < buf.len() {
+        match file.read(&mut buf[filled..]) {
+            Ok(0) => return Err(Error::RngFailure),
+            Ok(read) => filled += read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(_) => return Err(Error::RngFailure),
+        }
+    }
+    Ok(())
+}
+
+/// fills the `buf` with the `getrandom` syscall and falls back to `/dev/urandom` if it's unavailable.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn os_random(buf: &mut [u8]) -> Result<(), Error> {
+    getrandom(buf).unwrap_or_else(|| urandom(buf))
+}
+
+/// fills the `buf` from `/dev/urandom`.
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "android"))))]
+pub(crate) fn os_random(buf: &mut [u8]) -> Result<(), Error> {
+    urandom(buf)
+}
+
+#[cfg(not(unix))]
+compile_error!("The `pure-rust` feature is only supported on unix targets");
+
+#[cfg(test)]
+mod test {
+    use super::urandom;
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_getrandom() {
+        let mut buf = vec![0; 4 * 1024 * 1024 + 15];
+        super::getrandom(&mut buf).unwrap().unwrap();
+        assert!(buf.iter().any(|b| *b != 0));
+    }
+
+    #[test]
+    fn test_urandom() {
+        let mut buf = vec![0; 4 * 1024 * 1024 + 15];
+        urandom(&mut buf).unwrap();
+        assert!(buf.iter().any(|b| *b != 0));
+    }
+}
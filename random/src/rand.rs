@@ -0,0 +1,134 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::OsRng;
+use primitives::{rng::SecureRng, Error};
+use rand_core::{impls, CryptoRng, RngCore};
+
+/// An adapter that exposes a `SecureRng` as a `rand_core::RngCore + CryptoRng`. A failing `SecureRng` makes
+/// `fill_bytes` panic and `try_fill_bytes` return a `rand_core::Error` that wraps the `primitives::Error`.
+pub struct AsRngCore<R: SecureRng>(pub R);
+
+impl<R: SecureRng> RngCore for AsRngCore<R> {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(dest) {
+            panic!("The secure RNG failed: {}", e);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.0.random(dest).map_err(rand_core::Error::new)
+    }
+}
+
+impl<R: SecureRng> CryptoRng for AsRngCore<R> {}
+
+/// An adapter that exposes a `rand_core::RngCore + CryptoRng` as a `SecureRng`. Errors are mapped back to the
+/// `primitives::Error` they wrap or to `Error::RngFailure`.
+pub struct AsSecureRng<R: RngCore + CryptoRng>(pub R);
+
+impl<R: RngCore + CryptoRng> SecureRng for AsSecureRng<R> {
+    /// fill the `buf` with random bytes.
+    fn random(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        self.0
+            .try_fill_bytes(buf)
+            .map_err(|e| e.inner().downcast_ref::<Error>().copied().unwrap_or(Error::RngFailure))
+    }
+}
+
+impl RngCore for OsRng {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(dest) {
+            panic!("The OS RNG failed: {}", e);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.random(dest).map_err(rand_core::Error::new)
+    }
+}
+
+impl CryptoRng for OsRng {}
+
+#[cfg(test)]
+mod test {
+    use super::{AsRngCore, AsSecureRng};
+    use crate::{ChaChaRng, OsRng};
+    use primitives::{rng::SecureRng, Error};
+    use rand_core::{CryptoRng, RngCore};
+
+    // rng that always fails
+    struct FailingRng;
+    impl SecureRng for FailingRng {
+        fn random(&mut self, _buf: &mut [u8]) -> Result<(), Error> {
+            Err(Error::HealthTestFailed)
+        }
+    }
+
+    // a function from a third party crate that expects a rand_core RNG
+    fn third_party<R: RngCore + CryptoRng>(rng: &mut R) -> [u8; 32] {
+        let mut buf = [0; 32];
+        rng.fill_bytes(&mut buf);
+        buf
+    }
+
+    #[test]
+    fn test_os_rng() {
+        assert_ne!(third_party(&mut OsRng), [0; 32]);
+        assert_ne!(OsRng.next_u64(), OsRng.next_u64());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut expected = [0; 32];
+        ChaChaRng::from_seed(&[1; 32]).unwrap().random(&mut expected).unwrap();
+
+        let mut rng = AsRngCore(ChaChaRng::from_seed(&[1; 32]).unwrap());
+        assert_eq!(third_party(&mut rng), expected);
+
+        let mut buf = [0; 32];
+        let mut rng = AsSecureRng(AsRngCore(ChaChaRng::from_seed(&[1; 32]).unwrap()));
+        rng.random(&mut buf).unwrap();
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_error() {
+        let error = AsRngCore(FailingRng).try_fill_bytes(&mut [0; 32]).unwrap_err();
+        assert_eq!(error.inner().downcast_ref::<Error>(), Some(&Error::HealthTestFailed));
+
+        // the error survives the roundtrip
+        let error = AsSecureRng(AsRngCore(FailingRng)).random(&mut [0; 32]);
+        assert_eq!(error, Err(Error::HealthTestFailed));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fill_bytes_panics() {
+        AsRngCore(FailingRng).fill_bytes(&mut [0; 32]);
+    }
+}
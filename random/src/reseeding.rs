@@ -0,0 +1,239 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+use crate::OsRng;
+use primitives::{
+    rng::{DeterministicRng, SecureRng},
+    Error,
+};
+use std::time::{Duration, Instant};
+
+/// default number of bytes that are produced between two reseeds
+pub const RESEEDINGRNG_BYTES: usize = 64 * 1024;
+/// default time between two reseeds
+pub const RESEEDINGRNG_INTERVAL: Duration = Duration::from_secs(60);
+/// size of the seed that is drawn from the entropy source
+const RESEEDINGRNG_SEED: usize = 32;
+
+/// A userspace RNG that serves requests from a fast deterministic generator and reseeds it from an entropy source
+/// (the `OsRng` by default).
+///
+/// The generator is reseeded when it's created, once it produced the byte budget or the time budget ran out, and
+/// before it hands out bytes after a `fork()`, so that the parent and the child never share output. Forks are counted
+/// by a `pthread_atfork` child handler on unix and detected by a changed PID everywhere else.
+///
+/// A `ReseedingRng` is not `Sync`; a static RNG (e.g. for `BoxProvider::random_buf`) keeps one per thread and retries
+/// the initialization on the next call if it failed:
+///
+/// ```
+/// use random::{primitives::rng::SecureRng, ChaChaRng, ReseedingRng};
+/// use std::cell::RefCell;
+///
+/// thread_local! {
+///     static RNG: RefCell<Option<ReseedingRng<ChaChaRng>>> = RefCell::new(None);
+/// }
+///
+/// fn random_buf(buf: &mut [u8]) -> Result<(), random::primitives::Error> {
+///     RNG.with(|rng| {
+///         let mut rng = rng.borrow_mut();
+///         if rng.is_none() {
+///             *rng = Some(ReseedingRng::new(ChaChaRng::new()?)?);
+///         }
+///         rng.as_mut().expect("initialized above").random(buf)
+///     })
+/// }
+///
+/// let (mut a, mut b) = ([0; 32], [0; 32]);
+/// random_buf(&mut a).unwrap();
+/// random_buf(&mut b).unwrap();
+/// assert_ne!(a, b);
+/// ```
+pub struct ReseedingRng<R: DeterministicRng> {
+    rng: R,
+    source: Box<dyn SecureRng>,
+    byte_budget: usize,
+    interval: Duration,
+    bytes: usize,
+    reseeded_at: Instant,
+    generation: u64,
+}
+
+impl<R: DeterministicRng> ReseedingRng<R> {
+    /// wraps the `rng` and reseeds it from the OS's secure RNG
+    pub fn new(rng: R) -> Result<Self, Error> {
+        Self::with_source(rng, Box::new(OsRng))
+    }
+
+    /// wraps the `rng` and reseeds it from the `source`
+    pub fn with_source(rng: R, source: Box<dyn SecureRng>) -> Result<Self, Error> {
+        let mut this = Self {
+            rng,
+            source,
+            byte_budget: RESEEDINGRNG_BYTES,
+            interval: RESEEDINGRNG_INTERVAL,
+            bytes: 0,
+            reseeded_at: Instant::now(),
+            generation: fork::generation(),
+        };
+        this.reseed_now()?;
+        Ok(this)
+    }
+
+    /// sets the number of bytes (at least one) and the time after which the generator is reseeded.
+    pub fn with_budget(mut self, byte_budget: usize, interval: Duration) -> Self {
+        self.byte_budget = byte_budget.max(1);
+        self.interval = interval;
+        self
+    }
+
+    /// reseeds the generator from the entropy source and restarts both budgets
+    pub fn reseed_now(&mut self) -> Result<(), Error> {
+        let mut seed = [0; RESEEDINGRNG_SEED];
+        self.source.random(&mut seed)?;
+        self.rng.reseed(&seed)?;
+        seed.iter_mut().for_each(|b| *b = 0);
+
+        self.bytes = 0;
+        self.reseeded_at = Instant::now();
+        self.generation = fork::generation();
+        Ok(())
+    }
+
+    /// returns `true` if a budget ran out or the process was forked since the last reseed
+    fn needs_reseed(&self) -> bool {
+        self.bytes >= self.byte_budget
+            || self.reseeded_at.elapsed() >= self.interval
+            || self.generation != fork::generation()
+    }
+}
+
+#[cfg(unix)]
+mod fork {
+    use std::sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Once,
+    };
+
+    static REGISTER: Once = Once::new();
+    static REGISTERED: AtomicBool = AtomicBool::new(false);
+    static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+    unsafe extern "C" fn child() {
+        GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// returns a value that changes in the child of every `fork()`; the handler is registered on the first call and
+    /// the PID is used instead if that fails
+    pub fn generation() -> u64 {
+        REGISTER.call_once(|| {
+            let registered = unsafe { libc::pthread_atfork(None, None, Some(child)) } == 0;
+            REGISTERED.store(registered, Ordering::Relaxed);
+        });
+        match REGISTERED.load(Ordering::Relaxed) {
+            true => GENERATION.load(Ordering::Relaxed),
+            false => std::process::id() as u64,
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod fork {
+    /// returns a value that changes in a forked child
+    pub fn generation() -> u64 {
+        std::process::id() as u64
+    }
+}
+
+impl<R: DeterministicRng> SecureRng for ReseedingRng<R> {
+    /// fill the `buf` with random bytes and reseed whenever the byte budget runs out.
+    fn random(&mut self, mut buf: &mut [u8]) -> Result<(), Error> {
+        while !buf.is_empty() {
+            if self.needs_reseed() {
+                self.reseed_now()?;
+            }
+
+            let len = buf.len().min(self.byte_budget - self.bytes);
+            let (chunk, rest) = buf.split_at_mut(len);
+            self.rng.random(chunk)?;
+            self.bytes += len;
+            buf = rest;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ReseedingRng;
+    use crate::ChaChaRng;
+    use primitives::{rng::SecureRng, Error};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
+
+    // entropy source that counts how often it was used
+    struct CountingRng(Arc<AtomicUsize>);
+    impl SecureRng for CountingRng {
+        fn random(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+            let count = self.0.fetch_add(1, Ordering::SeqCst);
+            buf.iter_mut().for_each(|b| *b = count as u8);
+            Ok(())
+        }
+    }
+
+    fn counting_rng(byte_budget: usize, interval: Duration) -> (ReseedingRng<ChaChaRng>, Arc<AtomicUsize>) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let rng = ChaChaRng::from_seed(&[0; 32]).unwrap();
+        let rng = ReseedingRng::with_source(rng, Box::new(CountingRng(count.clone()))).unwrap();
+        (rng.with_budget(byte_budget, interval), count)
+    }
+
+    #[test]
+    fn test_byte_budget() {
+        let (mut rng, count) = counting_rng(1024, Duration::from_secs(3600));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        let mut buf = [0; 512];
+        rng.random(&mut buf).unwrap();
+        rng.random(&mut buf).unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        rng.random(&mut buf).unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+
+        // large requests are split at the budget
+        rng.random(&mut [0; 4096]).unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    fn test_time_budget() {
+        let (mut rng, count) = counting_rng(usize::MAX, Duration::from_millis(50));
+        rng.random(&mut [0; 32]).unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        thread::sleep(Duration::from_millis(60));
+        rng.random(&mut [0; 32]).unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_os_rng() {
+        let rng = ChaChaRng::from_seed(&[0; 32]).unwrap();
+        let (mut a, mut b) = (ReseedingRng::new(rng).unwrap(), [0; 32]);
+        a.random(&mut b).unwrap();
+        assert_ne!(b, [0; 32]);
+    }
+}
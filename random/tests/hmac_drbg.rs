@@ -0,0 +1,159 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+mod common;
+
+use common::JsonValueExt;
+use primitives::{
+    rng::{DeterministicRng, SecureRng},
+    Error,
+};
+use random::{HmacDrbg, HMACDRBG_MAX_REQUEST};
+use std::collections::VecDeque;
+
+// vector data.
+const VECTORS: &str = include_str!("hmac_drbg.json");
+// bytes returned by every generate call of the CAVP vectors
+const RETURNED: usize = 128;
+
+// entropy source that returns the queued inputs in order
+struct QueueRng(VecDeque<Vec<u8>>);
+impl SecureRng for QueueRng {
+    fn random(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        let next = self.0.pop_front().ok_or(Error::RngFailure)?;
+        assert_eq!(next.len(), buf.len());
+        buf.copy_from_slice(&next);
+        Ok(())
+    }
+}
+
+// struct for vector data
+#[derive(Debug)]
+struct TestVector {
+    id: String,
+    entropy: Vec<u8>,
+    nonce: Vec<u8>,
+    pers: Vec<u8>,
+    add: Vec<Vec<u8>>,
+    expected: Vec<u8>,
+}
+
+impl TestVector {
+    // load the json vectors of a section
+    pub fn load(section: &str, extra: impl Fn(&json::JsonValue) -> Vec<Vec<u8>>) -> Vec<(Self, Vec<Vec<u8>>)> {
+        let json = json::parse(VECTORS).unwrap();
+        let mut vecs = Vec::new();
+
+        for vec in json[section].check_array_iter() {
+            let this = Self {
+                id: vec["id"].check_string(),
+                entropy: vec["entropy"].check_bytes(),
+                nonce: vec["nonce"].check_bytes(),
+                pers: vec["pers"].check_bytes(),
+                add: vec["add"].check_array_iter().map(|add| add.check_bytes()).collect(),
+                expected: vec["expected"].check_bytes(),
+            };
+            vecs.push((this, extra(vec)));
+        }
+
+        vecs
+    }
+
+    // generates twice and compares the second output
+    pub fn test_generate(&self, mut drbg: HmacDrbg) -> &Self {
+        let mut buf = vec![0; RETURNED];
+        drbg.generate(&mut buf, &self.add[0]).unwrap();
+        drbg.generate(&mut buf, &self.add[1]).unwrap();
+        assert_eq!(buf, self.expected, "Vector: \"{}\"", self.id);
+
+        self
+    }
+}
+
+#[test]
+fn test_crypto() {
+    for (vec, _) in TestVector::load("crypto", |_| Vec::new()) {
+        vec.test_generate(HmacDrbg::instantiate(&vec.entropy, &vec.nonce, &vec.pers).unwrap());
+    }
+}
+
+#[test]
+fn test_reseed() {
+    let extra = |vec: &json::JsonValue| vec![vec["entropy_reseed"].check_bytes(), vec["add_reseed"].check_bytes()];
+    for (vec, reseed) in TestVector::load("reseed", extra) {
+        let mut drbg = HmacDrbg::instantiate(&vec.entropy, &vec.nonce, &vec.pers).unwrap();
+        drbg.reseed_with(&reseed[0], &reseed[1]).unwrap();
+        vec.test_generate(drbg);
+    }
+}
+
+#[test]
+fn test_prediction_resistance() {
+    let extra = |vec: &json::JsonValue| vec["entropy_pr"].check_array_iter().map(|e| e.check_bytes()).collect();
+    for (vec, entropy_pr) in TestVector::load("prediction_resistance", extra) {
+        let mut queue = VecDeque::new();
+        queue.push_back(vec.entropy.clone());
+        queue.push_back(vec.nonce.clone());
+        queue.extend(entropy_pr);
+
+        vec.test_generate(HmacDrbg::new(Box::new(QueueRng(queue)), &vec.pers, true).unwrap());
+    }
+}
+
+#[test]
+fn test_reseed_interval() {
+    let mut buf = [0; 32];
+
+    // without an entropy source the DRBG must be reseeded explicitly
+    let mut drbg = HmacDrbg::instantiate(&[0; 32], &[0; 16], &[])
+        .unwrap()
+        .with_reseed_interval(2);
+    drbg.generate(&mut buf, &[]).unwrap();
+    drbg.generate(&mut buf, &[]).unwrap();
+    assert_eq!(drbg.generate(&mut buf, &[]), Err(Error::ReseedRequired));
+    drbg.reseed(&[1; 32]).unwrap();
+    drbg.generate(&mut buf, &[]).unwrap();
+
+    // with an entropy source the DRBG reseeds itself
+    let queue = vec![vec![0; 32], vec![0; 16], vec![1; 32]].into_iter().collect();
+    let mut drbg = HmacDrbg::new(Box::new(QueueRng(queue)), &[], false)
+        .unwrap()
+        .with_reseed_interval(2);
+    drbg.random(&mut buf).unwrap();
+    drbg.random(&mut buf).unwrap();
+    drbg.random(&mut buf).unwrap();
+    assert_eq!(drbg.random(&mut buf), Ok(()));
+}
+
+#[test]
+fn test_api() {
+    let mut buf = vec![0; HMACDRBG_MAX_REQUEST + 1];
+
+    assert_eq!(
+        HmacDrbg::instantiate(&[0; 31], &[0; 16], &[]).err(),
+        Some(Error::InvalidSeedLength)
+    );
+    assert_eq!(
+        HmacDrbg::instantiate(&[0; 32], &[0; 15], &[]).err(),
+        Some(Error::InvalidNonceLength)
+    );
+
+    let mut drbg = HmacDrbg::instantiate(&[0; 32], &[0; 16], &[]).unwrap();
+    assert_eq!(drbg.generate(&mut buf, &[]), Err(Error::BufferTooLarge));
+    assert_eq!(drbg.reseed(&[0; 31]), Err(Error::InvalidSeedLength));
+
+    // requests larger than the max request size are split up
+    drbg.random(&mut buf).unwrap();
+    HmacDrbg::from_os_rng(b"personalization", true)
+        .unwrap()
+        .random(&mut buf)
+        .unwrap();
+}
@@ -0,0 +1,58 @@
+// Copyright 2020 IOTA Stiftung
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with
+// the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on
+// an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and limitations under the License.
+
+// this test runs without the test harness (`harness = false`): forking a process with the harness's worker threads
+// can deadlock the child on a lock held by another thread
+
+#[cfg(unix)]
+use primitives::rng::SecureRng;
+#[cfg(unix)]
+use random::{ChaChaRng, ReseedingRng};
+
+// the parent and a forked child must not produce the same bytes from the copied state
+#[cfg(unix)]
+fn test_fork() {
+    let mut rng = ReseedingRng::new(ChaChaRng::from_seed(&[0; 32]).unwrap()).unwrap();
+    rng.random(&mut [0; 32]).unwrap();
+
+    let mut fds = [0; 2];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+
+    let mut buf = [0u8; 32];
+    match unsafe { libc::fork() } {
+        -1 => panic!("fork failed"),
+        0 => {
+            // child: write the next bytes to the pipe and exit without unwinding into the parent's code
+            let status = match rng.random(&mut buf) {
+                Ok(()) => unsafe { libc::write(fds[1], buf.as_ptr() as *const _, buf.len()) != buf.len() as isize },
+                Err(_) => true,
+            };
+            unsafe { libc::_exit(status as i32) }
+        }
+        child => {
+            rng.random(&mut buf).unwrap();
+
+            let mut child_buf = [0u8; 32];
+            let read = unsafe { libc::read(fds[0], child_buf.as_mut_ptr() as *mut _, child_buf.len()) };
+            let mut status = 0;
+            unsafe { libc::waitpid(child, &mut status, 0) };
+
+            assert_eq!(read, child_buf.len() as isize);
+            assert!(libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0);
+            assert_ne!(buf, child_buf);
+        }
+    }
+}
+
+fn main() {
+    #[cfg(unix)]
+    test_fork();
+}
@@ -9,8 +9,9 @@
 // an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and limitations under the License.
 
+use crypto::{primitives::hash::Hash, Sha256};
 use sodiumoxide::crypto::{
-    hash, pwhash,
+    pwhash,
     secretstream::{self, Header, Key, Pull, Push, Stream, Tag},
 };
 
@@ -31,9 +32,12 @@ fn generate_salt() -> crate::Result<pwhash::Salt> {
     // generate salt
     let salt = pwhash::gen_salt();
     // hash salt with sha256
-    let hash = hash::sha256::hash(&salt.0);
+    let mut hash = [0; pwhash::SALTBYTES];
+    Sha256
+        .hash(&mut hash, &salt.0)
+        .map_err(|e| crate::Error::CryptoError(e.to_string()))?;
     // repack salt
-    let salt = pwhash::Salt::from_slice(hash.as_ref()).expect("Unable to rewrap salt");
+    let salt = pwhash::Salt(hash);
 
     Ok(salt)
 }
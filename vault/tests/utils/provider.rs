@@ -39,7 +39,7 @@ impl BoxProvider for Provider {
 
         XChaChaPoly
             .seal_with(cipher, data, ad, key.bytes(), nonce)
-            .map_err(|_| vault::Error::CryptoError(String::from("Unable to seal data")))?;
+            .map_err(|e| vault::Error::CryptoError(e.to_string()))?;
         Ok(boxx)
     }
     fn box_open(key: &Key<Self>, ad: &[u8], data: &[u8]) -> vault::Result<Vec<u8>> {
@@ -52,7 +52,7 @@ impl BoxProvider for Provider {
 
         XChaChaPoly
             .open_to(&mut plain, cipher, ad, key.bytes(), nonce)
-            .map_err(|_| vault::Error::CryptoError(String::from("Invalid Cipher")))?;
+            .map_err(|e| vault::Error::CryptoError(e.to_string()))?;
 
         Ok(plain)
     }
@@ -60,6 +60,6 @@ impl BoxProvider for Provider {
     fn random_buf(buf: &mut [u8]) -> vault::Result<()> {
         OsRng
             .random(buf)
-            .map_err(|_| vault::Error::CryptoError(String::from("Can't generated random Bytes")))
+            .map_err(|e| vault::Error::CryptoError(e.to_string()))
     }
 }